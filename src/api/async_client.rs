@@ -0,0 +1,247 @@
+use super::{
+    cache::Cache,
+    cached, decode,
+    endpoint::{self, Request},
+    error::Result,
+    rate_limit::RateLimiter,
+    retry::RetryPolicy,
+    store,
+    transport::{AsyncReqwestTransport, AsyncTransport, Response},
+    types::{
+        CollectionLog, Competition, Group, GroupMemberGain, HiscoreFilters, HiscoreRow, KillCounts,
+        MergedTimeline, Metric, NameChange, Period, PlayerData, PlayerGains, PlayerInformation,
+        PlayerRecords, PlayerStats, Skill, Skills, TimeRange, Timestamp,
+    },
+    ClientBuilder,
+};
+use futures_util::{stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+
+/// Async version of [`Client`](super::Client), exposing the same endpoints.
+///
+/// Requests are independent of each other, so many players can be fetched
+/// concurrently, e.g. with `futures::future::join_all` or `tokio::spawn`.
+pub struct AsyncClient {
+    base_url: String,
+    transport: Box<dyn AsyncTransport>,
+    cache: Option<Cache>,
+    rate_limiter: Option<RateLimiter>,
+    retry: RetryPolicy,
+}
+
+impl Default for AsyncClient {
+    fn default() -> Self {
+        Self::builder()
+            .build_async()
+            .expect("the default client configuration is valid")
+    }
+}
+
+impl AsyncClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the same builder as [`Client::builder`](super::Client::builder);
+    /// finish it with [`ClientBuilder::build_async`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: &Request) -> Result<T> {
+        let url = request.url(&self.base_url);
+        let result = match cached(self.cache.as_ref(), request, &url) {
+            Some(Ok(response)) => decode(&response, request.body),
+            Some(Err(error)) => Err(error),
+            None => match self.fetch(&url, request).await {
+                Ok(response) => store(self.cache.as_ref(), request, &url, &response),
+                Err(error) => Err(error),
+            },
+        };
+        request.map_result(result)
+    }
+
+    /// Sends a request once the rate limit allows it, retrying transient failures.
+    async fn fetch(&self, url: &str, request: &Request) -> Result<Response> {
+        let retry = if request.retryable {
+            self.retry
+        } else {
+            RetryPolicy::none()
+        };
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.rate_limiter {
+                tokio::time::sleep(limiter.reserve()).await;
+            }
+            let result = self.transport.get(url, &request.query).await;
+            match retry.delay(attempt, &result) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return result,
+            }
+            attempt += 1;
+        }
+    }
+
+    pub async fn player_information(&self, player: &str) -> Result<PlayerInformation> {
+        self.send(&endpoint::player_information(player)).await
+    }
+
+    /// Skill datapoints in `range`, see [`TimeRange`].
+    pub async fn player_datapoints(
+        &self,
+        player: &str,
+        range: TimeRange,
+    ) -> Result<BTreeMap<Timestamp, Skills>> {
+        self.send(&endpoint::player_datapoints(player, range))
+            .await
+            .map(|datapoints| range.clip(datapoints))
+    }
+
+    /// Previous names of the player.
+    pub async fn player_names(&self, player: &str) -> Result<Vec<NameChange>> {
+        self.send(&endpoint::player_names(player)).await
+    }
+
+    /// See [`Client::merged_player_datapoints`](super::Client::merged_player_datapoints).
+    pub async fn merged_player_datapoints(
+        &self,
+        player: &str,
+        range: TimeRange,
+    ) -> Result<MergedTimeline> {
+        let renames = self.player_names(player).await?;
+        let mut old = Vec::new();
+        for (name, renamed_at) in MergedTimeline::old_names(&renames, player) {
+            match self.player_datapoints(&name, range).await {
+                Ok(timeline) => old.push((renamed_at, timeline)),
+                Err(error) if error.is_player_error() => {}
+                Err(error) => return Err(error),
+            }
+        }
+        let current = self.player_datapoints(player, range).await?;
+        Ok(MergedTimeline::merge(current, old, renames))
+    }
+
+    /// See [`Client::batch_player_data`](super::Client::batch_player_data).
+    pub async fn batch_player_data<S: AsRef<str>>(
+        &self,
+        players: &[S],
+        range: TimeRange,
+        concurrency: usize,
+    ) -> Vec<(String, Result<PlayerData>)> {
+        stream::iter(players)
+            .map(|player| async move {
+                let player = player.as_ref();
+                (player.to_owned(), self.player_data(player, range).await)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    async fn player_data(&self, player: &str, range: TimeRange) -> Result<PlayerData> {
+        Ok(PlayerData {
+            information: self.player_information(player).await?,
+            datapoints: self.player_datapoints(player, range).await?,
+        })
+    }
+
+    /// Boss, clue scroll and minigame datapoints, see [`KillCounts`].
+    pub async fn player_kill_count_datapoints(
+        &self,
+        player: &str,
+        range: TimeRange,
+    ) -> Result<BTreeMap<Timestamp, KillCounts>> {
+        self.send(&endpoint::player_kill_count_datapoints(player, range))
+            .await
+            .map(|datapoints| range.clip(datapoints))
+    }
+
+    /// See [`Client::add_datapoint`](super::Client::add_datapoint).
+    pub async fn add_datapoint(&self, player: &str) -> Result<()> {
+        self.send::<()>(&endpoint::add_datapoint(player)).await?;
+        if let Some(cache) = &self.cache {
+            cache.invalidate_player(player);
+        }
+        Ok(())
+    }
+
+    pub async fn player_stats(&self, player: &str) -> Result<PlayerStats> {
+        self.send(&endpoint::player_stats(player)).await
+    }
+
+    pub async fn player_gains(&self, player: &str, period: Period) -> Result<PlayerGains> {
+        self.send(&endpoint::player_gains(player, period)).await
+    }
+
+    /// Best day, week and month gains in every skill and boss.
+    pub async fn player_records(&self, player: &str) -> Result<PlayerRecords> {
+        self.send(&endpoint::player_records(player)).await
+    }
+
+    pub async fn collection_log(&self, player: &str) -> Result<CollectionLog> {
+        self.send(&endpoint::collection_log(player)).await
+    }
+
+    pub async fn competition_info(&self, id: u32) -> Result<Competition> {
+        self.send(&endpoint::competition_info(id)).await
+    }
+
+    /// Usernames of the members of a group.
+    pub async fn group_members(&self, id: u32) -> Result<Vec<String>> {
+        self.send(&endpoint::group_members(id)).await
+    }
+
+    pub async fn group_info(&self, id: u32) -> Result<Group> {
+        self.send(&endpoint::group_info(id)).await
+    }
+
+    /// Gains of every member of a group in `metric` over `period`.
+    pub async fn group_gains(
+        &self,
+        id: u32,
+        metric: &Metric,
+        period: Period,
+    ) -> Result<Vec<GroupMemberGain>> {
+        self.send(&endpoint::group_gains(id, metric, period)).await
+    }
+
+    /// A page of the leaderboard of `skill`.
+    pub async fn skill_hiscores(
+        &self,
+        skill: Skill,
+        filters: &HiscoreFilters,
+    ) -> Result<Vec<HiscoreRow>> {
+        self.send(&endpoint::skill_hiscores(skill, filters)).await
+    }
+}
+
+impl ClientBuilder {
+    /// Ignored by [`ClientBuilder::build`].
+    pub fn async_transport(mut self, transport: impl AsyncTransport + 'static) -> Self {
+        self.async_transport = Some(Box::new(transport));
+        self
+    }
+
+    pub fn build_async(self) -> Result<AsyncClient> {
+        let transport = match self.async_transport {
+            Some(transport) => transport,
+            None => Box::new(AsyncReqwestTransport::new(&self.user_agent, self.timeout)?),
+        };
+        Ok(AsyncClient {
+            base_url: self.base_url,
+            transport,
+            cache: self.cache,
+            rate_limiter: self.rate_limit.map(RateLimiter::new),
+            retry: self.retry,
+        })
+    }
+}
@@ -0,0 +1,169 @@
+//! On-disk cache of API responses, see [`Cache`].
+
+use super::transport::Response;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// How long responses are served from the cache by default.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Stores successful responses on disk, keyed by URL and query parameters,
+/// so that they can be served again without a request until they're older
+/// than the TTL. In offline mode cached responses are served regardless of
+/// their age, and nothing else is requested.
+///
+/// Pass one to [`ClientBuilder::cache`](super::ClientBuilder::cache).
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+    ttl: Duration,
+    offline: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    key: String,
+    /// Seconds since the Unix epoch.
+    fetched_at: u64,
+    status: u16,
+    body: String,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: DEFAULT_TTL,
+            offline: false,
+        }
+    }
+
+    /// `temple-osrs` in the user's cache directory, or in the temporary
+    /// directory on platforms without one.
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(env!("CARGO_PKG_NAME"))
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Removes every cached response.
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// The cached response for the request, if there is one that's still
+    /// fresh. `max_age` shortens the TTL for data that changes quickly.
+    pub(crate) fn get(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+        max_age: Option<Duration>,
+    ) -> Option<Response> {
+        let key = key(url, query);
+        let entry: Entry = serde_json::from_slice(&fs::read(self.path(&key)).ok()?).ok()?;
+        // Guard against hash collisions.
+        if entry.key != key {
+            return None;
+        }
+        let age = Duration::from_secs(now().saturating_sub(entry.fetched_at));
+        let ttl = max_age.map_or(self.ttl, |max_age| max_age.min(self.ttl));
+        (self.offline || age <= ttl).then(|| Response::new(entry.status, entry.body))
+    }
+
+    /// Removes the cached responses about `player`, after their data changed.
+    pub(crate) fn invalidate_player(&self, player: &str) {
+        let Ok(files) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for path in files.filter_map(|file| Some(file.ok()?.path())) {
+            let Some(entry) = fs::read(&path)
+                .ok()
+                .and_then(|contents| serde_json::from_slice::<Entry>(&contents).ok())
+            else {
+                continue;
+            };
+            let about_player = entry
+                .key
+                .split_once('?')
+                .map_or("", |(_, query)| query)
+                .split('&')
+                .filter_map(|pair| pair.strip_prefix("player="))
+                .any(|name| name.eq_ignore_ascii_case(player));
+            if about_player {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+
+    /// Stores a response. Failing to write the cache isn't worth failing the
+    /// request over, so errors are ignored.
+    pub(crate) fn put(&self, url: &str, query: &[(&str, String)], response: &Response) {
+        let key = key(url, query);
+        let entry = Entry {
+            fetched_at: now(),
+            status: response.status,
+            body: response.body.clone(),
+            key,
+        };
+        let Ok(contents) = serde_json::to_vec(&entry) else {
+            return;
+        };
+        let _ =
+            fs::create_dir_all(&self.dir).and_then(|()| fs::write(self.path(&entry.key), contents));
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.json", fnv1a(key.as_bytes())))
+    }
+}
+
+fn key(url: &str, query: &[(&str, String)]) -> String {
+    let mut key = url.to_owned();
+    for (index, (name, value)) in query.iter().enumerate() {
+        key.push(if index == 0 { '?' } else { '&' });
+        key.push_str(name);
+        key.push('=');
+        key.push_str(value);
+    }
+    key
+}
+
+/// 64-bit FNV-1a, used for file names since it, unlike the standard library's
+/// hasher, is stable across Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
@@ -0,0 +1,192 @@
+//! Descriptions of the TempleOSRS endpoints, shared by the blocking and async clients.
+
+use super::{
+    error::Result,
+    player_error,
+    types::{HiscoreFilters, Metric, Period, Skill, TimeRange},
+};
+use chrono::Utc;
+use std::time::Duration;
+
+/// How the body of a successful response is laid out.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Body {
+    /// Wrapped in `{"data": ...}`, like most endpoints.
+    Enveloped,
+    /// The bare data.
+    Bare,
+    /// Not JSON, only the status matters.
+    Ignored,
+}
+
+pub(crate) struct Request {
+    pub path: &'static str,
+    pub query: Vec<(&'static str, String)>,
+    pub body: Body,
+    /// Whether the response may be served from a [`Cache`](super::cache::Cache).
+    pub cacheable: bool,
+    /// How long a cached response stays fresh, if shorter than the cache's TTL.
+    pub max_age: Option<Duration>,
+    /// Whether failures may be retried, see [`RetryPolicy`](super::retry::RetryPolicy).
+    pub retryable: bool,
+    player: Option<String>,
+}
+
+impl Request {
+    fn new(path: &'static str) -> Self {
+        Self {
+            path,
+            query: Vec::new(),
+            body: Body::Enveloped,
+            cacheable: true,
+            max_age: None,
+            retryable: true,
+            player: None,
+        }
+    }
+
+    fn body(mut self, body: Body) -> Self {
+        self.body = body;
+        self
+    }
+
+    fn uncached(mut self) -> Self {
+        self.cacheable = false;
+        self
+    }
+
+    /// For requests with side effects, which might have gone through even if
+    /// the response didn't make it back.
+    fn no_retry(mut self) -> Self {
+        self.retryable = false;
+        self
+    }
+
+    fn fresh_for(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    fn query(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.query.push((key, value.to_string()));
+        self
+    }
+
+    /// Adds the `player` query parameter, and remembers the player so that
+    /// errors can be reported as being about them.
+    fn player(mut self, player: &str) -> Self {
+        self.player = Some(player.to_owned());
+        self.query("player", player)
+    }
+
+    pub fn url(&self, base_url: &str) -> String {
+        format!("{base_url}/{}", self.path)
+    }
+
+    pub fn map_result<T>(&self, result: Result<T>) -> Result<T> {
+        match &self.player {
+            Some(player) => result.map_err(|error| player_error(player, error)),
+            None => result,
+        }
+    }
+}
+
+pub(crate) fn player_information(player: &str) -> Request {
+    // The datapoint cooldown counts down from when the response was sent,
+    // so a cached one would be off by its age.
+    Request::new("api/player_info.php")
+        .player(player)
+        .fresh_for(Duration::from_secs(5))
+}
+
+pub(crate) fn player_datapoints(player: &str, range: TimeRange) -> Request {
+    let request = Request::new("api/player_datapoints.php")
+        .player(player)
+        .query("time", range.as_seconds(Utc::now()));
+    match range {
+        // The query is different every time since it's relative to now, so
+        // caching it would only leave files behind.
+        TimeRange::Between { .. } => request.uncached(),
+        TimeRange::AllTime | TimeRange::Last(_) => request,
+    }
+}
+
+pub(crate) fn player_kill_count_datapoints(player: &str, range: TimeRange) -> Request {
+    player_datapoints(player, range).query("bosses", 1)
+}
+
+pub(crate) fn player_stats(player: &str) -> Request {
+    Request::new("api/player_stats.php").player(player)
+}
+
+pub(crate) fn player_gains(player: &str, period: Period) -> Request {
+    Request::new("api/player_gains.php")
+        .player(player)
+        .query("time", period.as_query())
+        .query("bosses", 1)
+}
+
+pub(crate) fn player_records(player: &str) -> Request {
+    Request::new("api/player_records.php")
+        .player(player)
+        .query("bosses", 1)
+}
+
+pub(crate) fn collection_log(player: &str) -> Request {
+    Request::new("api/collection-log/player_collection_log.php")
+        .player(player)
+        .query("categories", "all")
+        .query("includenames", 1)
+        .query("includemissingitems", 1)
+}
+
+pub(crate) fn competition_info(id: u32) -> Request {
+    // Standings change constantly while the competition is running. This is
+    // well below the TUI's one minute refresh, which is timed from when the
+    // request starts rather than when the cached response arrived.
+    Request::new("api/competition_info.php")
+        .query("id", id)
+        .fresh_for(Duration::from_secs(30))
+}
+
+pub(crate) fn group_members(id: u32) -> Request {
+    Request::new("api/groupmembers.php")
+        .query("id", id)
+        .body(Body::Bare)
+}
+
+pub(crate) fn group_info(id: u32) -> Request {
+    Request::new("api/group_info.php").query("id", id)
+}
+
+pub(crate) fn group_gains(id: u32, metric: &Metric, period: Period) -> Request {
+    Request::new("api/group_gains.php")
+        .query("id", id)
+        .query("skill", metric.key())
+        .query("time", period.as_query())
+}
+
+pub(crate) fn skill_hiscores(skill: Skill, filters: &HiscoreFilters) -> Request {
+    let mut request = Request::new("api/skill_hiscores.php")
+        .query("skill", skill.name())
+        .query("page", filters.page);
+    if let Some(game_mode) = filters.game_mode {
+        request = request.query("gamemode", game_mode.id());
+    }
+    if let Some(country) = &filters.country {
+        request = request.query("country", country);
+    }
+    request
+}
+
+pub(crate) fn add_datapoint(player: &str) -> Request {
+    Request::new("php/add_datapoint.php")
+        .player(player)
+        .body(Body::Ignored)
+        .uncached()
+        .no_retry()
+}
+
+pub(crate) fn player_names(player: &str) -> Request {
+    Request::new("api/player_names.php").player(player)
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+pub type Result<T, E = ApiError> = std::result::Result<T, E>;
+
+/// Everything that can go wrong when talking to TempleOSRS.
+#[derive(Error, Debug)]
+pub enum ApiError {
+    #[error("request timed out")]
+    Timeout,
+    #[error("network error: {0}")]
+    Network(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("unexpected HTTP status {status}")]
+    Status { status: u16 },
+    #[error("TempleOSRS reported an error: {0}")]
+    Api(ErrorPayload),
+    #[error("failed to decode response at `{path}`: {source}")]
+    Decode {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("player \"{player}\" not found")]
+    PlayerNotFound {
+        player: String,
+        payload: Option<ErrorPayload>,
+    },
+    #[error("{url} is not cached, and the client is offline")]
+    Offline { url: String },
+    #[error("player \"{player}\" is not tracked by TempleOSRS yet")]
+    PlayerNotTracked {
+        player: String,
+        payload: ErrorPayload,
+    },
+}
+
+impl ApiError {
+    /// Short, human readable name of the variant, used for display in the TUI.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Timeout => "Timeout",
+            Self::Network(_) => "Network error",
+            Self::Status { status: 429 } => "Rate limited",
+            Self::Status { status } if *status >= 500 => "Server error",
+            Self::Status { .. } => "HTTP error",
+            Self::Api(_) => "API error",
+            Self::Decode { .. } => "Decode error",
+            Self::PlayerNotFound { .. } => "Player not found",
+            Self::PlayerNotTracked { .. } => "Player not tracked",
+            Self::Offline { .. } => "Offline",
+        }
+    }
+
+    /// The error payload reported by TempleOSRS, if the error came from one.
+    pub fn payload(&self) -> Option<&ErrorPayload> {
+        match self {
+            Self::Api(payload) | Self::PlayerNotTracked { payload, .. } => Some(payload),
+            Self::PlayerNotFound { payload, .. } => payload.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the error means there is no data for the requested player.
+    pub fn is_player_error(&self) -> bool {
+        matches!(
+            self,
+            Self::PlayerNotFound { .. } | Self::PlayerNotTracked { .. }
+        )
+    }
+
+    /// Whether the request might succeed if it's sent again, see
+    /// [`RetryPolicy`](super::retry::RetryPolicy).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Timeout | Self::Network(_) => true,
+            Self::Status { status } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    pub fn is_player_not_tracked(&self) -> bool {
+        matches!(self, Self::PlayerNotTracked { .. })
+    }
+}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout
+        } else {
+            Self::Network(Box::new(error))
+        }
+    }
+}
+
+/// The contents of the `{"error": {...}}` envelope TempleOSRS responds with
+/// instead of `{"data": ...}` when a request fails.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct ErrorPayload {
+    #[serde(default, alias = "code", skip_serializing_if = "Option::is_none")]
+    pub code: Option<i64>,
+    #[serde(default, alias = "message")]
+    pub message: String,
+}
+
+impl ErrorPayload {
+    pub fn kind(&self) -> ErrorPayloadKind {
+        let message = self.message.to_lowercase();
+        if message.contains("not tracked")
+            || message.contains("not in database")
+            || message.contains("not in our database")
+        {
+            ErrorPayloadKind::PlayerNotTracked
+        } else if message.contains("not found") || message.contains("does not exist") {
+            ErrorPayloadKind::PlayerNotFound
+        } else if message.contains("invalid") || message.contains("parameter") {
+            ErrorPayloadKind::InvalidParameter
+        } else {
+            ErrorPayloadKind::Other
+        }
+    }
+}
+
+impl fmt::Display for ErrorPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "{} (code {code})", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// Classification of an [`ErrorPayload`], derived from its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPayloadKind {
+    PlayerNotFound,
+    PlayerNotTracked,
+    InvalidParameter,
+    Other,
+}
@@ -1,18 +1,166 @@
-use self::types::{PlayerInformation, Skills, Timestamp};
-use anyhow::Result;
-use serde::Deserialize;
-use std::collections::BTreeMap;
+use self::{
+    cache::Cache,
+    endpoint::{Body, Request},
+    error::{ErrorPayload, ErrorPayloadKind, Result},
+    rate_limit::{RateLimit, RateLimiter},
+    retry::RetryPolicy,
+    transport::{ReqwestTransport, Response, Transport},
+    types::{
+        CollectionLog, Competition, Group, GroupMemberGain, HiscoreFilters, HiscoreRow, KillCounts,
+        MergedTimeline, Metric, NameChange, Period, PlayerData, PlayerGains, PlayerInformation,
+        PlayerRecords, PlayerStats, Skill, Skills, TimeRange, Timestamp,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
 
+#[cfg(feature = "async")]
+pub use self::async_client::AsyncClient;
+pub use self::error::ApiError;
+
+#[cfg(feature = "async")]
+mod async_client;
+pub mod cache;
+mod endpoint;
+pub mod error;
+pub mod rate_limit;
+pub mod retry;
+pub mod transport;
 pub mod types;
+pub mod xp;
+
+pub const DEFAULT_BASE_URL: &str = "https://templeosrs.com";
+pub const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
 #[derive(Deserialize, Debug)]
 struct Data<T> {
     data: T,
 }
 
-#[derive(Default)]
+#[derive(Deserialize, Debug)]
+struct ErrorEnvelope {
+    error: RawErrorPayload,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum RawErrorPayload {
+    Payload(ErrorPayload),
+    Message(String),
+}
+
+impl From<RawErrorPayload> for ErrorPayload {
+    fn from(raw: RawErrorPayload) -> Self {
+        match raw {
+            RawErrorPayload::Payload(payload) => payload,
+            RawErrorPayload::Message(message) => Self {
+                code: None,
+                message,
+            },
+        }
+    }
+}
+
+fn decode<T: DeserializeOwned>(response: &Response, body: Body) -> Result<T> {
+    if let Ok(ErrorEnvelope { error }) = serde_json::from_str(&response.body) {
+        return Err(ApiError::Api(error.into()));
+    }
+    if !(200..300).contains(&response.status) {
+        return Err(ApiError::Status {
+            status: response.status,
+        });
+    }
+    let deserializer = &mut serde_json::Deserializer::from_str(&response.body);
+    let result = match body {
+        Body::Enveloped => {
+            serde_path_to_error::deserialize::<_, Data<T>>(deserializer).map(|data| data.data)
+        }
+        Body::Bare => serde_path_to_error::deserialize::<_, T>(deserializer),
+        Body::Ignored => serde_path_to_error::deserialize::<_, T>(serde_json::Value::Null),
+    };
+    result.map_err(|error| ApiError::Decode {
+        path: error.path().to_string(),
+        source: error.into_inner(),
+    })
+}
+
+/// Turns errors that are about the requested player into
+/// [`ApiError::PlayerNotFound`] or [`ApiError::PlayerNotTracked`].
+fn player_error(player: &str, error: ApiError) -> ApiError {
+    match error {
+        ApiError::Status { status: 404 } => ApiError::PlayerNotFound {
+            player: player.to_owned(),
+            payload: None,
+        },
+        ApiError::Api(payload) => match payload.kind() {
+            ErrorPayloadKind::PlayerNotFound => ApiError::PlayerNotFound {
+                player: player.to_owned(),
+                payload: Some(payload),
+            },
+            ErrorPayloadKind::PlayerNotTracked => ApiError::PlayerNotTracked {
+                player: player.to_owned(),
+                payload,
+            },
+            _ => ApiError::Api(payload),
+        },
+        error => error,
+    }
+}
+
+/// Looks the request up in the cache. `None` means it should be sent.
+fn cached(cache: Option<&Cache>, request: &Request, url: &str) -> Option<Result<Response>> {
+    let cache = cache?;
+    let response = if request.cacheable {
+        cache.get(url, &request.query, request.max_age)
+    } else {
+        None
+    };
+    match response {
+        Some(response) => Some(Ok(response)),
+        None if cache.is_offline() => Some(Err(ApiError::Offline {
+            url: url.to_owned(),
+        })),
+        None => None,
+    }
+}
+
+/// Decodes a fresh response, caching it if that succeeds.
+fn store<T: DeserializeOwned>(
+    cache: Option<&Cache>,
+    request: &Request,
+    url: &str,
+    response: &Response,
+) -> Result<T> {
+    let value = decode(response, request.body)?;
+    if let Some(cache) = cache.filter(|_| request.cacheable) {
+        cache.put(url, &request.query, response);
+    }
+    Ok(value)
+}
+
 pub struct Client {
-    client: reqwest::blocking::Client,
+    base_url: String,
+    transport: Box<dyn Transport>,
+    cache: Option<Cache>,
+    rate_limiter: Option<RateLimiter>,
+    retry: RetryPolicy,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::builder()
+            .build()
+            .expect("the default client configuration is valid")
+    }
 }
 
 impl Client {
@@ -20,27 +168,735 @@ impl Client {
         Self::default()
     }
 
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    fn send<T: DeserializeOwned>(&self, request: &Request) -> Result<T> {
+        let url = request.url(&self.base_url);
+        let result = match cached(self.cache.as_ref(), request, &url) {
+            Some(Ok(response)) => decode(&response, request.body),
+            Some(Err(error)) => Err(error),
+            None => self
+                .fetch(&url, request)
+                .and_then(|response| store(self.cache.as_ref(), request, &url, &response)),
+        };
+        request.map_result(result)
+    }
+
+    /// Sends a request once the rate limit allows it, retrying transient failures.
+    fn fetch(&self, url: &str, request: &Request) -> Result<Response> {
+        let retry = if request.retryable {
+            self.retry
+        } else {
+            RetryPolicy::none()
+        };
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.rate_limiter {
+                thread::sleep(limiter.reserve());
+            }
+            let result = self.transport.get(url, &request.query);
+            match retry.delay(attempt, &result) {
+                Some(delay) => thread::sleep(delay),
+                None => return result,
+            }
+            attempt += 1;
+        }
+    }
+
     pub fn player_information(&self, player: &str) -> Result<PlayerInformation> {
-        Ok(self
-            .client
-            .get("https://templeosrs.com/api/player_info.php")
-            .query(&[("player", player)])
-            .send()?
-            .json::<Data<PlayerInformation>>()?
-            .data)
+        self.send(&endpoint::player_information(player))
     }
 
+    /// Skill datapoints in `range`, see [`TimeRange`].
     pub fn player_datapoints(
         &self,
         player: &str,
-        time: u32,
+        range: TimeRange,
     ) -> Result<BTreeMap<Timestamp, Skills>> {
-        Ok(self
-            .client
-            .get("https://templeosrs.com/api/player_datapoints.php")
-            .query(&[("player", player), ("time", &time.to_string())])
-            .send()?
-            .json::<Data<BTreeMap<Timestamp, Skills>>>()?
-            .data)
+        self.send(&endpoint::player_datapoints(player, range))
+            .map(|datapoints| range.clip(datapoints))
+    }
+
+    /// Previous names of the player.
+    pub fn player_names(&self, player: &str) -> Result<Vec<NameChange>> {
+        self.send(&endpoint::player_names(player))
+    }
+
+    /// Datapoints of the player under their current name and every previous
+    /// name, merged into a single timeline. Old names that TempleOSRS has no
+    /// data for are skipped.
+    pub fn merged_player_datapoints(
+        &self,
+        player: &str,
+        range: TimeRange,
+    ) -> Result<MergedTimeline> {
+        let renames = self.player_names(player)?;
+        let old = MergedTimeline::old_names(&renames, player)
+            .into_iter()
+            .map(
+                |(name, renamed_at)| match self.player_datapoints(&name, range) {
+                    Ok(timeline) => Ok((renamed_at, timeline)),
+                    Err(error) if error.is_player_error() => Ok((renamed_at, BTreeMap::new())),
+                    Err(error) => Err(error),
+                },
+            )
+            .collect::<Result<Vec<_>>>()?;
+        let current = self.player_datapoints(player, range)?;
+        Ok(MergedTimeline::merge(current, old, renames))
+    }
+
+    /// Information and skill datapoints in `range` of every player, fetching
+    /// up to `concurrency` players at a time. Results are in the same order
+    /// as `players`, and a player that fails doesn't affect the others.
+    ///
+    /// The [rate limit](ClientBuilder::rate_limit) still applies, so it's the
+    /// better knob for how fast requests are sent.
+    pub fn batch_player_data<S: AsRef<str> + Sync>(
+        &self,
+        players: &[S],
+        range: TimeRange,
+        concurrency: usize,
+    ) -> Vec<(String, Result<PlayerData>)> {
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(players.len()));
+        thread::scope(|scope| {
+            for _ in 0..concurrency.clamp(1, players.len().max(1)) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(player) = players.get(index) else {
+                        break;
+                    };
+                    let result = self.player_data(player.as_ref(), range);
+                    results
+                        .lock()
+                        .expect("no worker panicked while holding the lock")
+                        .push((index, result));
+                });
+            }
+        });
+
+        let mut results = results
+            .into_inner()
+            .expect("no worker panicked while holding the lock");
+        results.sort_by_key(|&(index, _)| index);
+        results
+            .into_iter()
+            .map(|(index, result)| (players[index].as_ref().to_owned(), result))
+            .collect()
+    }
+
+    fn player_data(&self, player: &str, range: TimeRange) -> Result<PlayerData> {
+        Ok(PlayerData {
+            information: self.player_information(player)?,
+            datapoints: self.player_datapoints(player, range)?,
+        })
+    }
+
+    /// Boss, clue scroll and minigame datapoints, see [`KillCounts`].
+    pub fn player_kill_count_datapoints(
+        &self,
+        player: &str,
+        range: TimeRange,
+    ) -> Result<BTreeMap<Timestamp, KillCounts>> {
+        self.send(&endpoint::player_kill_count_datapoints(player, range))
+            .map(|datapoints| range.clip(datapoints))
+    }
+
+    /// Asks TempleOSRS to fetch a new datapoint for the player from the
+    /// official hiscores, which also starts tracking players that aren't
+    /// tracked yet. Respect [`PlayerInformation::datapoint_cooldown`] before
+    /// calling this again.
+    pub fn add_datapoint(&self, player: &str) -> Result<()> {
+        self.send::<()>(&endpoint::add_datapoint(player))?;
+        if let Some(cache) = &self.cache {
+            cache.invalidate_player(player);
+        }
+        Ok(())
+    }
+
+    pub fn player_stats(&self, player: &str) -> Result<PlayerStats> {
+        self.send(&endpoint::player_stats(player))
+    }
+
+    pub fn player_gains(&self, player: &str, period: Period) -> Result<PlayerGains> {
+        self.send(&endpoint::player_gains(player, period))
+    }
+
+    /// Best day, week and month gains in every skill and boss.
+    pub fn player_records(&self, player: &str) -> Result<PlayerRecords> {
+        self.send(&endpoint::player_records(player))
+    }
+
+    pub fn collection_log(&self, player: &str) -> Result<CollectionLog> {
+        self.send(&endpoint::collection_log(player))
+    }
+
+    pub fn competition_info(&self, id: u32) -> Result<Competition> {
+        self.send(&endpoint::competition_info(id))
+    }
+
+    /// Usernames of the members of a group.
+    pub fn group_members(&self, id: u32) -> Result<Vec<String>> {
+        self.send(&endpoint::group_members(id))
+    }
+
+    pub fn group_info(&self, id: u32) -> Result<Group> {
+        self.send(&endpoint::group_info(id))
+    }
+
+    /// Gains of every member of a group in `metric` over `period`.
+    pub fn group_gains(
+        &self,
+        id: u32,
+        metric: &Metric,
+        period: Period,
+    ) -> Result<Vec<GroupMemberGain>> {
+        self.send(&endpoint::group_gains(id, metric, period))
+    }
+
+    /// A page of the leaderboard of `skill`.
+    pub fn skill_hiscores(
+        &self,
+        skill: Skill,
+        filters: &HiscoreFilters,
+    ) -> Result<Vec<HiscoreRow>> {
+        self.send(&endpoint::skill_hiscores(skill, filters))
+    }
+}
+
+/// Builder for [`Client`], obtained through [`Client::builder`].
+pub struct ClientBuilder {
+    base_url: String,
+    user_agent: String,
+    timeout: Option<Duration>,
+    cache: Option<Cache>,
+    rate_limit: Option<RateLimit>,
+    retry: RetryPolicy,
+    transport: Option<Box<dyn Transport>>,
+    #[cfg(feature = "async")]
+    async_transport: Option<Box<dyn transport::AsyncTransport>>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            user_agent: DEFAULT_USER_AGENT.to_owned(),
+            timeout: None,
+            cache: None,
+            rate_limit: None,
+            retry: RetryPolicy::default(),
+            transport: None,
+            #[cfg(feature = "async")]
+            async_transport: None,
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Root URL that endpoint paths such as `api/player_info.php` are appended to.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Ignored when a custom [`Transport`] is supplied.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Ignored when a custom [`Transport`] is supplied.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Serve responses from `cache` when possible, and store new ones in it.
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Limit how fast requests are sent. Responses served from the cache
+    /// don't count towards the limit.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// Defaults to [`RetryPolicy::default`], use [`RetryPolicy::none`] to
+    /// send every request only once.
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Only used by [`ClientBuilder::build`].
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+
+    pub fn build(self) -> Result<Client> {
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => Box::new(ReqwestTransport::new(&self.user_agent, self.timeout)?),
+        };
+        Ok(Client {
+            base_url: self.base_url,
+            transport,
+            cache: self.cache,
+            rate_limiter: self.rate_limit.map(RateLimiter::new),
+            retry: self.retry,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::{
+        collections::HashMap,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
+
+    const BASE_URL: &str = "http://templeosrs.test";
+
+    /// Path, and the player if the response is only for them.
+    type ResponseKey = (&'static str, Option<String>);
+
+    /// Answers requests with canned responses keyed by path, and optionally
+    /// by player, 404 for anything else. Counts the requests it gets, and
+    /// how many of them were in flight at once.
+    #[derive(Clone, Default)]
+    struct FakeTransport {
+        responses: Arc<Mutex<HashMap<ResponseKey, Response>>>,
+        delays: Arc<Mutex<HashMap<String, Duration>>>,
+        requests: Arc<AtomicUsize>,
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl FakeTransport {
+        fn respond(&self, path: &'static str, status: u16, body: &str) {
+            self.responses
+                .lock()
+                .unwrap()
+                .insert((path, None), Response::new(status, body));
+        }
+
+        fn respond_to(&self, path: &'static str, player: &str, status: u16, body: &str) {
+            self.responses
+                .lock()
+                .unwrap()
+                .insert((path, Some(player.to_owned())), Response::new(status, body));
+        }
+
+        /// Makes requests about `player` take `delay`.
+        fn delay(&self, player: &str, delay: Duration) {
+            self.delays.lock().unwrap().insert(player.to_owned(), delay);
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+
+        fn max_in_flight(&self) -> usize {
+            self.max_in_flight.load(Ordering::SeqCst)
+        }
+    }
+
+    impl FakeTransport {
+        /// Starts a request, returning how long it should take.
+        fn start(&self, query: &[(&str, String)]) -> Duration {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            player(query)
+                .and_then(|player| self.delays.lock().unwrap().get(&player).copied())
+                .unwrap_or_default()
+        }
+
+        fn finish(&self, url: &str, query: &[(&str, String)]) -> Response {
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            let path = url.strip_prefix(BASE_URL).unwrap().trim_start_matches('/');
+            let responses = self.responses.lock().unwrap();
+            responses
+                .get(&(path, player(query)))
+                .or_else(|| responses.get(&(path, None)))
+                .cloned()
+                .unwrap_or_else(|| Response::new(404, ""))
+        }
+    }
+
+    fn player(query: &[(&str, String)]) -> Option<String> {
+        query
+            .iter()
+            .find(|(key, _)| *key == "player")
+            .map(|(_, player)| player.clone())
+    }
+
+    impl Transport for FakeTransport {
+        fn get(&self, url: &str, query: &[(&str, String)]) -> Result<Response> {
+            thread::sleep(self.start(query));
+            Ok(self.finish(url, query))
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl transport::AsyncTransport for FakeTransport {
+        fn get<'a>(
+            &'a self,
+            url: &'a str,
+            query: &'a [(&'a str, String)],
+        ) -> transport::BoxFuture<'a, Result<Response>> {
+            Box::pin(async move {
+                tokio::time::sleep(self.start(query)).await;
+                Ok(self.finish(url, query))
+            })
+        }
+    }
+
+    fn client(transport: &FakeTransport) -> Client {
+        Client::builder()
+            .base_url(BASE_URL)
+            .retry(RetryPolicy::none())
+            .transport(transport.clone())
+            .build()
+            .unwrap()
+    }
+
+    /// A cache in a directory of its own, removed again when dropped.
+    struct TempCache(Cache);
+
+    impl TempCache {
+        fn new(name: &str) -> Self {
+            let dir: PathBuf = std::env::temp_dir().join(format!(
+                "{}-test-{}-{name}",
+                env!("CARGO_PKG_NAME"),
+                std::process::id()
+            ));
+            let cache = Cache::new(dir);
+            cache.clear().unwrap();
+            Self(cache)
+        }
+    }
+
+    impl Drop for TempCache {
+        fn drop(&mut self) {
+            let _ = self.0.clear();
+        }
+    }
+
+    fn cached_client(transport: &FakeTransport, cache: Cache) -> Client {
+        Client::builder()
+            .base_url(BASE_URL)
+            .retry(RetryPolicy::none())
+            .cache(cache)
+            .transport(transport.clone())
+            .build()
+            .unwrap()
+    }
+
+    const NAMES: &str = r#"{"data": [
+        {"old_name": "Old", "new_name": "New", "time_changed": "2023-08-10 12:34:56"}
+    ]}"#;
+
+    #[test]
+    fn decodes_enveloped_data() {
+        let transport = FakeTransport::default();
+        transport.respond("api/player_names.php", 200, NAMES);
+        let names = client(&transport).player_names("New").unwrap();
+        assert_eq!(names.len(), 1);
+        assert_eq!(names[0].old_name, "Old");
+    }
+
+    #[test]
+    fn decodes_bare_and_ignored_bodies() {
+        let transport = FakeTransport::default();
+        transport.respond("api/groupmembers.php", 200, r#"["Lynx Titan", "Zezima"]"#);
+        transport.respond("php/add_datapoint.php", 200, "Datapoint added");
+        let client = client(&transport);
+        assert_eq!(client.group_members(1).unwrap(), ["Lynx Titan", "Zezima"]);
+        client.add_datapoint("Lynx Titan").unwrap();
+    }
+
+    #[test]
+    fn reports_where_decoding_failed() {
+        let transport = FakeTransport::default();
+        transport.respond(
+            "api/player_names.php",
+            200,
+            r#"{"data": [{"old_name": 1}]}"#,
+        );
+        match client(&transport).player_names("New") {
+            Err(ApiError::Decode { path, .. }) => assert_eq!(path, "data[0].old_name"),
+            result => panic!("expected a decode error, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn maps_player_errors() {
+        let transport = FakeTransport::default();
+        let client = client(&transport);
+
+        transport.respond(
+            "api/player_info.php",
+            200,
+            r#"{"error": {"Code": 402, "Message": "Player not found"}}"#,
+        );
+        match client.player_information("Nobody") {
+            Err(ApiError::PlayerNotFound {
+                player,
+                payload: Some(payload),
+            }) => {
+                assert_eq!(player, "Nobody");
+                assert_eq!(payload.code, Some(402));
+            }
+            result => panic!("expected PlayerNotFound, got {result:?}"),
+        }
+
+        transport.respond(
+            "api/player_info.php",
+            200,
+            r#"{"error": "Player is not in our database"}"#,
+        );
+        assert!(matches!(
+            client.player_information("Nobody"),
+            Err(ApiError::PlayerNotTracked { .. })
+        ));
+
+        // Unknown paths answer with a bare 404.
+        assert!(matches!(
+            client.player_stats("Nobody"),
+            Err(ApiError::PlayerNotFound { payload: None, .. })
+        ));
+    }
+
+    #[test]
+    fn keeps_other_errors() {
+        let transport = FakeTransport::default();
+        transport.respond(
+            "api/group_info.php",
+            200,
+            r#"{"error": {"Code": 400, "Message": "Invalid group id"}}"#,
+        );
+        transport.respond("api/player_stats.php", 500, "");
+        let client = client(&transport);
+        match client.group_info(1) {
+            Err(ApiError::Api(payload)) => assert_eq!(payload.message, "Invalid group id"),
+            result => panic!("expected an API error, got {result:?}"),
+        }
+        assert!(matches!(
+            client.player_stats("Lynx Titan"),
+            Err(ApiError::Status { status: 500 })
+        ));
+    }
+
+    fn information(player: &str) -> String {
+        json!({"data": {
+            "Username": player,
+            "Country": "US",
+            "Game mode": 0,
+            "fresh_start_account": 0,
+            "Cb-3": 0,
+            "F2p": 0,
+            "Banned": 0,
+            "Disqualified": 0,
+            "Clan preference": null,
+            "Last checked": "2023-08-10 12:34:56",
+            "Last changed": "2023-08-09 08:00:00",
+            "Last changed KC": null,
+            "Datapoint Cooldown": "00:00:00",
+        }})
+        .to_string()
+    }
+
+    /// Players `0` to `count`, where `3` doesn't exist and the first ones
+    /// take the longest, so they finish out of order.
+    fn batch_transport(count: usize) -> (FakeTransport, Vec<String>) {
+        let transport = FakeTransport::default();
+        transport.respond("api/player_datapoints.php", 200, r#"{"data": {}}"#);
+        let players: Vec<String> = (0..count).map(|index| index.to_string()).collect();
+        for (index, player) in players.iter().enumerate() {
+            if player != "3" {
+                transport.respond_to("api/player_info.php", player, 200, &information(player));
+            }
+            let delay = u64::try_from(count - index).unwrap();
+            transport.delay(player, Duration::from_millis(delay * 5));
+        }
+        (transport, players)
+    }
+
+    fn assert_batch(players: &[String], results: &[(String, Result<PlayerData>)]) {
+        let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, players);
+        for (player, result) in results {
+            match result {
+                Err(ApiError::PlayerNotFound {
+                    player: missing, ..
+                }) => {
+                    assert_eq!(player, "3");
+                    assert_eq!(missing, "3");
+                }
+                Ok(data) => assert_eq!(&data.information.username, player),
+                Err(error) => panic!("unexpected error for {player}: {error}"),
+            }
+        }
+    }
+
+    #[test]
+    fn batch_keeps_order_and_per_player_errors() {
+        let (transport, players) = batch_transport(8);
+        let results = client(&transport).batch_player_data(&players, TimeRange::AllTime, 3);
+        assert_batch(&players, &results);
+        assert!(results[3].1.is_err());
+        assert_eq!(
+            results.iter().filter(|(_, result)| result.is_err()).count(),
+            1
+        );
+        // Player 3 fails on its first request.
+        assert_eq!(transport.requests(), 7 * 2 + 1);
+        assert_eq!(transport.max_in_flight(), 3);
+    }
+
+    #[test]
+    fn batch_clamps_concurrency() {
+        let (transport, players) = batch_transport(4);
+        let results = client(&transport).batch_player_data(&players, TimeRange::AllTime, 0);
+        assert_batch(&players, &results);
+        assert_eq!(transport.max_in_flight(), 1);
+
+        let (transport, players) = batch_transport(4);
+        let results = client(&transport).batch_player_data(&players, TimeRange::AllTime, 100);
+        assert_batch(&players, &results);
+        assert_eq!(transport.max_in_flight(), 4);
+
+        let none: [&str; 0] = [];
+        assert!(client(&transport)
+            .batch_player_data(&none, TimeRange::AllTime, 4)
+            .is_empty());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_batch_keeps_order_and_per_player_errors() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let (transport, players) = batch_transport(8);
+        let client = Client::builder()
+            .base_url(BASE_URL)
+            .retry(RetryPolicy::none())
+            .async_transport(transport.clone())
+            .build_async()
+            .unwrap();
+        let results = runtime.block_on(client.batch_player_data(&players, TimeRange::AllTime, 3));
+        assert_batch(&players, &results);
+        assert_eq!(transport.max_in_flight(), 3);
+
+        let (transport, players) = batch_transport(4);
+        let client = Client::builder()
+            .base_url(BASE_URL)
+            .async_transport(transport.clone())
+            .build_async()
+            .unwrap();
+        let results = runtime.block_on(client.batch_player_data(&players, TimeRange::AllTime, 0));
+        assert_batch(&players, &results);
+        assert_eq!(transport.max_in_flight(), 1);
+    }
+
+    #[test]
+    fn retries_only_retryable_requests() {
+        let transport = FakeTransport::default();
+        transport.respond("api/player_names.php", 503, "");
+        transport.respond("php/add_datapoint.php", 503, "");
+        let client = Client::builder()
+            .base_url(BASE_URL)
+            .retry(
+                RetryPolicy::default()
+                    .max_retries(2)
+                    .base_delay(Duration::ZERO),
+            )
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        assert!(client.player_names("Lynx Titan").is_err());
+        assert_eq!(transport.requests(), 3);
+        assert!(client.add_datapoint("Lynx Titan").is_err());
+        assert_eq!(transport.requests(), 4);
+    }
+
+    #[test]
+    fn serves_responses_from_the_cache() {
+        let cache = TempCache::new("cache");
+        let transport = FakeTransport::default();
+        transport.respond("api/player_names.php", 200, NAMES);
+        transport.respond("php/add_datapoint.php", 200, "");
+        let client = cached_client(&transport, cache.0.clone());
+
+        client.player_names("New").unwrap();
+        client.player_names("New").unwrap();
+        assert_eq!(transport.requests(), 1);
+
+        // Other players and uncacheable requests aren't served from the cache.
+        client.player_names("Other").unwrap();
+        client.add_datapoint("Other").unwrap();
+        client.add_datapoint("Other").unwrap();
+        assert_eq!(transport.requests(), 4);
+
+        // A new datapoint invalidates the player's responses.
+        client.add_datapoint("New").unwrap();
+        client.player_names("New").unwrap();
+        assert_eq!(transport.requests(), 6);
+    }
+
+    #[test]
+    fn does_not_cache_errors() {
+        let cache = TempCache::new("errors");
+        let transport = FakeTransport::default();
+        let client = cached_client(&transport, cache.0.clone());
+        assert!(client.player_names("Nobody").is_err());
+        assert!(client.player_names("Nobody").is_err());
+        assert_eq!(transport.requests(), 2);
+    }
+
+    #[test]
+    fn offline_only_serves_the_cache() {
+        let cache = TempCache::new("offline");
+        let transport = FakeTransport::default();
+        transport.respond("api/player_names.php", 200, NAMES);
+        cached_client(&transport, cache.0.clone())
+            .player_names("New")
+            .unwrap();
+
+        // Offline, even stale responses are served.
+        let offline = cache.0.clone().ttl(Duration::ZERO).offline(true);
+        let client = cached_client(&transport, offline);
+        assert_eq!(client.player_names("New").unwrap().len(), 1);
+        assert!(matches!(
+            client.player_names("Other"),
+            Err(ApiError::Offline { .. })
+        ));
+        assert!(matches!(
+            client.add_datapoint("New"),
+            Err(ApiError::Offline { .. })
+        ));
+        assert_eq!(transport.requests(), 1);
     }
 }
@@ -0,0 +1,116 @@
+//! Client-side rate limiting, see [`RateLimit`].
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A token bucket: up to `burst` requests can be sent at once, after which
+/// requests are spread out to `requests` per `per`.
+///
+/// Pass one to [`ClientBuilder::rate_limit`](super::ClientBuilder::rate_limit).
+/// The limit is shared by every endpoint of the client, and requests that
+/// have to wait are queued in the order they were made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    requests: u32,
+    per: Duration,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// `requests` per `per`, with a burst of the same size.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        let requests = requests.max(1);
+        Self {
+            requests,
+            per,
+            burst: requests,
+        }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// How many requests can be sent back to back before they get spread out.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// Time it takes for one token to be added to the bucket.
+    fn interval(self) -> Duration {
+        self.per / self.requests
+    }
+}
+
+/// Hands out the tokens of a [`RateLimit`].
+///
+/// Rather than counting tokens, this keeps track of when the bucket will be
+/// full again. Every request pushes that point one interval further, so
+/// callers that have to wait are given increasingly later turns.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    full_at: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            full_at: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Takes a token, returning how long to wait before the request may be sent.
+    pub fn reserve(&self) -> Duration {
+        let now = Instant::now();
+        let interval = self.limit.interval();
+        let mut full_at = self
+            .full_at
+            .lock()
+            .expect("the rate limiter is not poisoned");
+        let start = (*full_at).max(now);
+        *full_at = start + interval;
+        // The bucket holds `burst` tokens, so a request only waits once it
+        // would be more than that many intervals ahead.
+        (start + interval).saturating_duration_since(now + interval * self.limit.burst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waits_grow_after_burst() {
+        // Long enough that the time the test takes doesn't matter.
+        let interval = Duration::from_secs(10);
+        let limiter = RateLimiter::new(RateLimit::per_minute(6).burst(3));
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve(), Duration::ZERO);
+        }
+        for turn in 1..=3 {
+            let wait = limiter.reserve();
+            let expected = interval * turn;
+            assert!(
+                wait <= expected && wait + Duration::from_secs(1) > expected,
+                "waited {wait:?} instead of {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn burst_defaults_to_rate() {
+        let limiter = RateLimiter::new(RateLimit::per_minute(2));
+        assert_eq!(limiter.reserve(), Duration::ZERO);
+        assert_eq!(limiter.reserve(), Duration::ZERO);
+        assert!(limiter.reserve() > Duration::ZERO);
+    }
+}
@@ -0,0 +1,181 @@
+//! Retrying transient failures, see [`RetryPolicy`].
+
+use super::{error::Result, transport::Response};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// How often and how long to wait before retrying requests that failed for
+/// reasons that are likely to go away: timeouts, network errors, server
+/// errors and rate limiting. Anything else, like an unknown player or a
+/// response that can't be decoded, is returned right away.
+///
+/// The wait doubles with every retry, up to `max_delay`, and is randomized
+/// so that many clients don't retry in lockstep. A `Retry-After` sent by
+/// the server is used instead when there is one, unless it's longer than
+/// `max_delay`, in which case the request isn't retried.
+///
+/// Pass one to [`ClientBuilder::retry`](super::ClientBuilder::retry).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub fn none() -> Self {
+        Self::default().max_retries(0)
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Wait before the first retry, before jitter.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// How long to wait before retrying after `attempt` (starting at 0)
+    /// ended in `result`, or `None` if it shouldn't be retried.
+    pub(crate) fn delay(&self, attempt: u32, result: &Result<Response>) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        match result {
+            Ok(response) if response.status == 429 || response.status >= 500 => {
+                match response.retry_after {
+                    Some(retry_after) if retry_after > self.max_delay => None,
+                    Some(retry_after) => Some(retry_after),
+                    None => Some(self.backoff(attempt)),
+                }
+            }
+            Ok(_) => None,
+            Err(error) if error.is_transient() => Some(self.backoff(attempt)),
+            Err(_) => None,
+        }
+    }
+
+    /// Somewhere between half of and the full exponential delay.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let jitter = f64::from(u32::try_from(random() % 1000).unwrap_or_default()) / 1000.;
+        delay / 2 + (delay / 2).mul_f64(jitter)
+    }
+}
+
+/// A random number, good enough for jitter without pulling in a dependency.
+/// Every `RandomState` is seeded differently.
+fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ApiError;
+
+    fn status(status: u16) -> Result<Response> {
+        Ok(Response::new(status, ""))
+    }
+
+    #[test]
+    fn retries_transient_failures() {
+        let policy = RetryPolicy::default();
+        assert!(policy.delay(0, &status(429)).is_some());
+        assert!(policy.delay(0, &status(500)).is_some());
+        assert!(policy.delay(0, &status(503)).is_some());
+        assert!(policy.delay(0, &Err(ApiError::Timeout)).is_some());
+        assert!(policy
+            .delay(0, &Err(ApiError::Network("reset".into())))
+            .is_some());
+        assert!(policy
+            .delay(0, &Err(ApiError::Status { status: 502 }))
+            .is_some());
+    }
+
+    #[test]
+    fn returns_other_results_right_away() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(0, &status(200)), None);
+        assert_eq!(policy.delay(0, &status(404)), None);
+        let not_found = ApiError::PlayerNotFound {
+            player: "Lynx Titan".to_owned(),
+            payload: None,
+        };
+        assert_eq!(policy.delay(0, &Err(not_found)), None);
+        let decode = ApiError::Decode {
+            path: ".".to_owned(),
+            source: serde_json::from_str::<u32>("").unwrap_err(),
+        };
+        assert_eq!(policy.delay(0, &Err(decode)), None);
+    }
+
+    #[test]
+    fn honours_retry_after() {
+        let policy = RetryPolicy::default().max_delay(Duration::from_secs(10));
+        let retry_after =
+            |seconds| Ok(Response::new(429, "").retry_after(Some(Duration::from_secs(seconds))));
+        assert_eq!(
+            policy.delay(0, &retry_after(5)),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            policy.delay(2, &retry_after(10)),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(policy.delay(0, &retry_after(11)), None);
+    }
+
+    #[test]
+    fn stops_after_max_retries() {
+        let policy = RetryPolicy::default().max_retries(2);
+        assert!(policy.delay(1, &status(500)).is_some());
+        assert_eq!(policy.delay(2, &status(500)), None);
+        assert_eq!(RetryPolicy::none().delay(0, &status(500)), None);
+    }
+
+    #[test]
+    fn backoff_is_jittered_within_bounds() {
+        let base = Duration::from_millis(100);
+        let policy = RetryPolicy::default()
+            .base_delay(base)
+            .max_delay(Duration::from_millis(500))
+            .max_retries(10);
+        for attempt in 0..10 {
+            let delay = base.saturating_mul(1 << attempt).min(policy.max_delay);
+            for _ in 0..100 {
+                let backoff = policy.delay(attempt, &status(500)).unwrap();
+                assert!(
+                    (delay / 2..=delay).contains(&backoff),
+                    "{backoff:?} outside of [{:?}, {delay:?}]",
+                    delay / 2
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use super::error::Result;
+use std::time::Duration;
+
+/// A raw HTTP response as returned by a [`Transport`].
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+    /// The `Retry-After` header, if the server sent one in seconds.
+    pub retry_after: Option<Duration>,
+}
+
+impl Response {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+            retry_after: None,
+        }
+    }
+
+    pub fn retry_after(mut self, retry_after: Option<Duration>) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+}
+
+/// Parses a `Retry-After` header given in seconds. The HTTP date form isn't
+/// supported.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let seconds = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    Some(Duration::from_secs(seconds.trim().parse().ok()?))
+}
+
+/// Performs the HTTP requests for a [`Client`](super::Client).
+///
+/// Implement this to point the client at something other than the network,
+/// e.g. recorded fixtures or an in-memory fake backend. Failures to reach the
+/// backend at all should be reported as [`ApiError::Timeout`] or
+/// [`ApiError::Network`]; non-2xx statuses are returned as regular responses.
+///
+/// [`ApiError::Timeout`]: super::error::ApiError::Timeout
+/// [`ApiError::Network`]: super::error::ApiError::Network
+pub trait Transport: Send + Sync {
+    fn get(&self, url: &str, query: &[(&str, String)]) -> Result<Response>;
+}
+
+/// The default [`Transport`], backed by a blocking `reqwest` client.
+pub struct ReqwestTransport {
+    client: reqwest::blocking::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(user_agent: &str, timeout: Option<Duration>) -> Result<Self> {
+        let mut builder = reqwest::blocking::Client::builder().user_agent(user_agent);
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        Ok(Self {
+            client: builder.build()?,
+        })
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn get(&self, url: &str, query: &[(&str, String)]) -> Result<Response> {
+        let response = self.client.get(url).query(query).send()?;
+        let retry_after = retry_after(response.headers());
+        Ok(Response::new(response.status().as_u16(), response.text()?).retry_after(retry_after))
+    }
+}
+
+#[cfg(feature = "async")]
+pub type BoxFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
+
+/// The async counterpart of [`Transport`], used by
+/// [`AsyncClient`](super::AsyncClient).
+#[cfg(feature = "async")]
+pub trait AsyncTransport: Send + Sync {
+    fn get<'a>(
+        &'a self,
+        url: &'a str,
+        query: &'a [(&'a str, String)],
+    ) -> BoxFuture<'a, Result<Response>>;
+}
+
+/// The default [`AsyncTransport`], backed by an async `reqwest` client.
+#[cfg(feature = "async")]
+pub struct AsyncReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "async")]
+impl AsyncReqwestTransport {
+    pub fn new(user_agent: &str, timeout: Option<Duration>) -> Result<Self> {
+        let mut builder = reqwest::Client::builder().user_agent(user_agent);
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        Ok(Self {
+            client: builder.build()?,
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for AsyncReqwestTransport {
+    fn get<'a>(
+        &'a self,
+        url: &'a str,
+        query: &'a [(&'a str, String)],
+    ) -> BoxFuture<'a, Result<Response>> {
+        Box::pin(async move {
+            let response = self.client.get(url).query(query).send().await?;
+            let retry_after = retry_after(response.headers());
+            Ok(
+                Response::new(response.status().as_u16(), response.text().await?)
+                    .retry_after(retry_after),
+            )
+        })
+    }
+}
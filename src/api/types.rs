@@ -1,10 +1,155 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
-use serde::{de::Visitor, Deserialize};
-use serde_repr::Deserialize_repr;
+use serde::{de::Visitor, ser::SerializeMap, Deserialize, Serialize, Serializer};
+use serde_json::{Number, Value};
 use serde_with::{serde_as, BoolFromInt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    time::Duration,
+};
+
+/// A skill, in hiscores order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Skill {
+    #[default]
+    Overall,
+    Attack,
+    Defence,
+    Strength,
+    Hitpoints,
+    Ranged,
+    Prayer,
+    Magic,
+    Cooking,
+    Woodcutting,
+    Fletching,
+    Fishing,
+    Firemaking,
+    Crafting,
+    Smithing,
+    Mining,
+    Herblore,
+    Agility,
+    Thieving,
+    Slayer,
+    Farming,
+    Runecraft,
+    Hunter,
+    Construction,
+}
+
+/// The groups skills are sorted into in the skill guides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillCategory {
+    Combat,
+    Gathering,
+    Artisan,
+    Support,
+}
+
+impl Skill {
+    pub const ALL: [Skill; 24] = [
+        Skill::Overall,
+        Skill::Attack,
+        Skill::Defence,
+        Skill::Strength,
+        Skill::Hitpoints,
+        Skill::Ranged,
+        Skill::Prayer,
+        Skill::Magic,
+        Skill::Cooking,
+        Skill::Woodcutting,
+        Skill::Fletching,
+        Skill::Fishing,
+        Skill::Firemaking,
+        Skill::Crafting,
+        Skill::Smithing,
+        Skill::Mining,
+        Skill::Herblore,
+        Skill::Agility,
+        Skill::Thieving,
+        Skill::Slayer,
+        Skill::Farming,
+        Skill::Runecraft,
+        Skill::Hunter,
+        Skill::Construction,
+    ];
+
+    /// Name of the skill in TempleOSRS requests and responses.
+    pub fn name(self) -> &'static str {
+        match self {
+            Skill::Overall => "Overall",
+            Skill::Attack => "Attack",
+            Skill::Defence => "Defence",
+            Skill::Strength => "Strength",
+            Skill::Hitpoints => "Hitpoints",
+            Skill::Ranged => "Ranged",
+            Skill::Prayer => "Prayer",
+            Skill::Magic => "Magic",
+            Skill::Cooking => "Cooking",
+            Skill::Woodcutting => "Woodcutting",
+            Skill::Fletching => "Fletching",
+            Skill::Fishing => "Fishing",
+            Skill::Firemaking => "Firemaking",
+            Skill::Crafting => "Crafting",
+            Skill::Smithing => "Smithing",
+            Skill::Mining => "Mining",
+            Skill::Herblore => "Herblore",
+            Skill::Agility => "Agility",
+            Skill::Thieving => "Thieving",
+            Skill::Slayer => "Slayer",
+            Skill::Farming => "Farming",
+            Skill::Runecraft => "Runecraft",
+            Skill::Hunter => "Hunter",
+            Skill::Construction => "Construction",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|skill| skill.name() == name)
+    }
+
+    /// `None` for [`Skill::Overall`], which isn't a skill of its own.
+    pub fn category(self) -> Option<SkillCategory> {
+        match self {
+            Skill::Overall => None,
+            Skill::Attack
+            | Skill::Defence
+            | Skill::Strength
+            | Skill::Hitpoints
+            | Skill::Ranged
+            | Skill::Prayer
+            | Skill::Magic => Some(SkillCategory::Combat),
+            Skill::Woodcutting
+            | Skill::Fishing
+            | Skill::Mining
+            | Skill::Farming
+            | Skill::Hunter => Some(SkillCategory::Gathering),
+            Skill::Cooking
+            | Skill::Fletching
+            | Skill::Firemaking
+            | Skill::Crafting
+            | Skill::Smithing
+            | Skill::Herblore
+            | Skill::Runecraft
+            | Skill::Construction => Some(SkillCategory::Artisan),
+            Skill::Agility | Skill::Thieving | Skill::Slayer => Some(SkillCategory::Support),
+        }
+    }
+
+    pub fn is_combat(self) -> bool {
+        self.category() == Some(SkillCategory::Combat)
+    }
+}
+
+impl fmt::Display for Skill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
 
 #[serde_as]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[allow(clippy::struct_excessive_bools)]
 #[serde(rename_all = "PascalCase")]
 pub struct PlayerInformation {
@@ -32,23 +177,141 @@ pub struct PlayerInformation {
     pub last_changed: Option<Timestamp>,
     #[serde(rename = "Last changed KC")]
     pub last_changed_kc: Option<Timestamp>,
-    #[serde(rename = "Datapoint Cooldown")]
-    pub datapoint_cooldown: String,
+    /// Time left until a new datapoint can be requested with
+    /// [`Client::add_datapoint`](super::Client::add_datapoint), as of when
+    /// the information was fetched.
+    #[serde(
+        rename = "Datapoint Cooldown",
+        deserialize_with = "deserialize_cooldown",
+        serialize_with = "serialize_cooldown"
+    )]
+    pub datapoint_cooldown: Duration,
+}
+
+/// Parses cooldowns such as `00:14:59`, `14:59` or `899` (seconds). Anything
+/// else, like `-`, means there is no cooldown.
+fn deserialize_cooldown<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let seconds = match Value::deserialize(deserializer)? {
+        Value::Number(seconds) => seconds.as_u64(),
+        Value::String(cooldown) => cooldown.trim().split(':').try_fold(0, |total: u64, part| {
+            Some(total * 60 + part.trim().parse::<u64>().ok()?)
+        }),
+        _ => None,
+    };
+    Ok(Duration::from_secs(seconds.unwrap_or_default()))
+}
+
+/// Writes cooldowns as `HH:MM:SS`, like TempleOSRS does.
+fn serialize_cooldown<S>(cooldown: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let seconds = cooldown.as_secs();
+    serializer.collect_str(&format_args!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    ))
 }
 
-#[derive(Deserialize_repr, Debug)]
-#[repr(u8)]
+/// Account type, sent by TempleOSRS as a number.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "u8", into = "u8")]
 pub enum GameMode {
-    Normal = 0,
-    Ironman = 1,
-    UltimateIronman = 2,
-    HardcoreIronman = 3,
+    Normal,
+    Ironman,
+    UltimateIronman,
+    HardcoreIronman,
+    GroupIronman,
+    HardcoreGroupIronman,
+    UnrankedGroupIronman,
+    /// A mode added after this version was released.
+    Unknown(u8),
+}
+
+impl GameMode {
+    /// Every game mode known to this version, in the order of their IDs.
+    pub const KNOWN: [GameMode; 7] = [
+        GameMode::Normal,
+        GameMode::Ironman,
+        GameMode::UltimateIronman,
+        GameMode::HardcoreIronman,
+        GameMode::GroupIronman,
+        GameMode::HardcoreGroupIronman,
+        GameMode::UnrankedGroupIronman,
+    ];
+
+    /// ID of the mode in TempleOSRS requests and responses.
+    pub fn id(self) -> u8 {
+        match self {
+            GameMode::Normal => 0,
+            GameMode::Ironman => 1,
+            GameMode::UltimateIronman => 2,
+            GameMode::HardcoreIronman => 3,
+            GameMode::GroupIronman => 4,
+            GameMode::HardcoreGroupIronman => 5,
+            GameMode::UnrankedGroupIronman => 6,
+            GameMode::Unknown(id) => id,
+        }
+    }
+
+    pub fn is_ironman(self) -> bool {
+        !matches!(self, GameMode::Normal | GameMode::Unknown(_))
+    }
+}
+
+impl From<u8> for GameMode {
+    fn from(id: u8) -> Self {
+        Self::KNOWN
+            .into_iter()
+            .find(|mode| mode.id() == id)
+            .unwrap_or(GameMode::Unknown(id))
+    }
+}
+
+impl From<GameMode> for u8 {
+    fn from(mode: GameMode) -> Self {
+        mode.id()
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GameMode::Normal => "Normal",
+            GameMode::Ironman => "Ironman",
+            GameMode::UltimateIronman => "Ultimate ironman",
+            GameMode::HardcoreIronman => "Hardcore ironman",
+            GameMode::GroupIronman => "Group ironman",
+            GameMode::HardcoreGroupIronman => "Hardcore group ironman",
+            GameMode::UnrankedGroupIronman => "Unranked group ironman",
+            GameMode::Unknown(id) => return write!(f, "Unknown ({id})"),
+        })
+    }
 }
 
 #[repr(transparent)]
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Timestamp(pub DateTime<Utc>);
 
+impl Timestamp {
+    /// The format TempleOSRS sends timestamps in.
+    const FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&self.0.format(Self::FORMAT))
+    }
+}
+
 impl<'de> Deserialize<'de> for Timestamp {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -68,7 +331,7 @@ impl<'de> Deserialize<'de> for Timestamp {
                 E: serde::de::Error,
             {
                 Ok(Timestamp(DateTime::from_utc(
-                    NaiveDateTime::parse_from_str(v, "%Y-%m-%d %H:%M:%S").map_err(|_| {
+                    NaiveDateTime::parse_from_str(v, Timestamp::FORMAT).map_err(|_| {
                         serde::de::Error::invalid_value(serde::de::Unexpected::Str(v), &self)
                     })?,
                     Utc,
@@ -80,7 +343,7 @@ impl<'de> Deserialize<'de> for Timestamp {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Skills {
     pub overall: u64,
@@ -108,4 +371,1448 @@ pub struct Skills {
     pub hunter: u32,
     pub construction: u32,
     pub ehp: f32,
+    /// Numeric fields this version doesn't know about yet, such as skills
+    /// added to the game after it was released, keyed by their name. Kept as
+    /// JSON numbers so integers are written back as integers.
+    #[serde(flatten, deserialize_with = "deserialize_numeric_fields")]
+    pub extra: BTreeMap<String, Number>,
+}
+
+/// Keeps the numeric values of a map and drops everything else.
+fn deserialize_numeric_fields<'de, D>(deserializer: D) -> Result<BTreeMap<String, Number>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(BTreeMap::<String, Value>::deserialize(deserializer)?
+        .into_iter()
+        .filter_map(|(name, value)| match value {
+            Value::Number(number) => Some((name, number)),
+            _ => None,
+        })
+        .collect())
+}
+
+impl Skills {
+    /// Value of a metric in [`Skills::extra`].
+    pub fn extra(&self, name: &str) -> Option<f64> {
+        self.extra.get(name)?.as_f64()
+    }
+
+    /// Experience in `skill`.
+    pub fn get(&self, skill: Skill) -> u64 {
+        match skill {
+            Skill::Overall => self.overall,
+            Skill::Attack => self.attack.into(),
+            Skill::Defence => self.defence.into(),
+            Skill::Strength => self.strength.into(),
+            Skill::Hitpoints => self.hitpoints.into(),
+            Skill::Ranged => self.ranged.into(),
+            Skill::Prayer => self.prayer.into(),
+            Skill::Magic => self.magic.into(),
+            Skill::Cooking => self.cooking.into(),
+            Skill::Woodcutting => self.woodcutting.into(),
+            Skill::Fletching => self.fletching.into(),
+            Skill::Fishing => self.fishing.into(),
+            Skill::Firemaking => self.firemaking.into(),
+            Skill::Crafting => self.crafting.into(),
+            Skill::Smithing => self.smithing.into(),
+            Skill::Mining => self.mining.into(),
+            Skill::Herblore => self.herblore.into(),
+            Skill::Agility => self.agility.into(),
+            Skill::Thieving => self.thieving.into(),
+            Skill::Slayer => self.slayer.into(),
+            Skill::Farming => self.farming.into(),
+            Skill::Runecraft => self.runecraft.into(),
+            Skill::Hunter => self.hunter.into(),
+            Skill::Construction => self.construction.into(),
+        }
+    }
+}
+
+/// Current stats of a player, as returned by `player_stats.php`.
+#[derive(Debug, PartialEq)]
+pub struct PlayerStats {
+    pub date: Option<Timestamp>,
+    /// One entry per skill, in the order of [`Skill::ALL`].
+    pub skills: Vec<SkillStats>,
+    pub ehp: f64,
+    pub ehp_rank: Option<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SkillStats {
+    pub skill: Skill,
+    pub xp: u64,
+    pub level: Option<u32>,
+    pub rank: Option<u32>,
+    pub ehp: f64,
+}
+
+impl PlayerStats {
+    pub fn skill(&self, skill: Skill) -> Option<&SkillStats> {
+        self.skills.iter().find(|stats| stats.skill == skill)
+    }
+}
+
+impl Serialize for PlayerStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(date) = &self.date {
+            map.serialize_entry("date", date)?;
+        }
+        for skill in &self.skills {
+            let name = skill.skill.name();
+            map.serialize_entry(name, &skill.xp)?;
+            if let Some(level) = skill.level {
+                map.serialize_entry(&format!("{name}_level"), &level)?;
+            }
+            if let Some(rank) = skill.rank {
+                map.serialize_entry(&format!("{name}_rank"), &rank)?;
+            }
+            map.serialize_entry(&format!("{name}_ehp"), &skill.ehp)?;
+        }
+        map.serialize_entry("Ehp", &self.ehp)?;
+        if let Some(rank) = self.ehp_rank {
+            map.serialize_entry("Ehp_rank", &rank)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayerStats {
+    /// The endpoint returns a flat object with keys such as `Attack`,
+    /// `Attack_level`, `Attack_rank` and `Attack_ehp` for every skill.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let fields = HashMap::<String, Value>::deserialize(deserializer)?;
+        let integer = |key: &str| {
+            fields
+                .get(key)
+                .and_then(Value::as_u64)
+                .and_then(|value| u32::try_from(value).ok())
+        };
+        let float = |key: &str| fields.get(key).and_then(Value::as_f64).unwrap_or_default();
+
+        let skills = Skill::ALL
+            .into_iter()
+            .map(|skill| {
+                let name = skill.name();
+                Ok(SkillStats {
+                    skill,
+                    xp: fields
+                        .get(name)
+                        .and_then(Value::as_u64)
+                        .ok_or_else(|| serde::de::Error::missing_field(name))?,
+                    level: integer(&format!("{name}_level")),
+                    rank: integer(&format!("{name}_rank")),
+                    ehp: float(&format!("{name}_ehp")),
+                })
+            })
+            .collect::<Result<_, D::Error>>()?;
+
+        Ok(Self {
+            date: fields
+                .get("date")
+                .and_then(|date| Timestamp::deserialize(date).ok()),
+            skills,
+            ehp: float("Ehp"),
+            ehp_rank: integer("Ehp_rank"),
+        })
+    }
+}
+
+/// Time period to compute gains over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+    Year,
+    /// The last given number of seconds.
+    Seconds(u32),
+}
+
+impl Period {
+    /// Value of the `time` query parameter for this period.
+    pub fn as_query(self) -> String {
+        match self {
+            Period::Day => "day".to_owned(),
+            Period::Week => "week".to_owned(),
+            Period::Month => "month".to_owned(),
+            Period::Year => "year".to_owned(),
+            Period::Seconds(seconds) => seconds.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Period::Day => f.write_str("Day"),
+            Period::Week => f.write_str("Week"),
+            Period::Month => f.write_str("Month"),
+            Period::Year => f.write_str("Year"),
+            Period::Seconds(seconds) => write!(f, "{seconds} seconds"),
+        }
+    }
+}
+
+/// Which datapoints to request from `player_datapoints.php`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeRange {
+    /// Every datapoint TempleOSRS has for the player.
+    #[default]
+    AllTime,
+    /// Datapoints from the given duration back until now.
+    Last(Duration),
+    /// Datapoints between two points in time, inclusive.
+    Between { start: Timestamp, end: Timestamp },
+}
+
+impl TimeRange {
+    /// The API has no all-time option, this reaches back to before TempleOSRS existed.
+    const ALL_TIME_SECONDS: u32 = 1_000_000_000;
+
+    /// Value of the `time` query parameter, the number of seconds back from
+    /// `now` to fetch datapoints for.
+    pub fn as_seconds(self, now: DateTime<Utc>) -> u32 {
+        let seconds = match self {
+            TimeRange::AllTime => return Self::ALL_TIME_SECONDS,
+            TimeRange::Last(duration) => duration.as_secs(),
+            TimeRange::Between { start, .. } => {
+                u64::try_from((now - start.0).num_seconds()).unwrap_or(0)
+            }
+        };
+        u32::try_from(seconds).map_or(Self::ALL_TIME_SECONDS, |seconds| {
+            seconds.min(Self::ALL_TIME_SECONDS)
+        })
+    }
+
+    /// Whether a datapoint taken at `timestamp` falls in the range. Only
+    /// [`TimeRange::Between`] has an upper bound.
+    pub fn contains(self, timestamp: Timestamp) -> bool {
+        match self {
+            TimeRange::AllTime | TimeRange::Last(_) => true,
+            TimeRange::Between { start, end } => (start..=end).contains(&timestamp),
+        }
+    }
+
+    /// Drops the datapoints outside the range, for the bounds the API can't express.
+    pub fn clip<T>(self, mut datapoints: BTreeMap<Timestamp, T>) -> BTreeMap<Timestamp, T> {
+        datapoints.retain(|timestamp, _| self.contains(*timestamp));
+        datapoints
+    }
+}
+
+/// Gains of a player over a [`Period`], as returned by `player_gains.php`.
+#[derive(Debug, PartialEq)]
+pub struct PlayerGains {
+    /// One entry per skill, in the order of [`Skill::ALL`].
+    pub skills: Vec<SkillGain>,
+    /// Every boss and activity with a kill count, sorted by name.
+    pub bosses: Vec<BossGain>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Default)]
+pub struct SkillGain {
+    #[serde(skip)]
+    pub skill: Skill,
+    #[serde(default)]
+    pub xp: i64,
+    #[serde(default)]
+    pub level: i32,
+    /// Negative when the player moved up the hiscores.
+    #[serde(default)]
+    pub rank: i64,
+    #[serde(default)]
+    pub ehp: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Default)]
+pub struct BossGain {
+    #[serde(skip)]
+    pub name: String,
+    pub kc: i64,
+    /// Negative when the player moved up the hiscores.
+    #[serde(default)]
+    pub rank: i64,
+    #[serde(default)]
+    pub ehb: f64,
+}
+
+impl PlayerGains {
+    pub fn skill(&self, skill: Skill) -> Option<&SkillGain> {
+        self.skills.iter().find(|gain| gain.skill == skill)
+    }
+
+    pub fn boss(&self, name: &str) -> Option<&BossGain> {
+        self.bosses
+            .iter()
+            .find(|boss| boss.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Serialize for PlayerGains {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.skills.len() + self.bosses.len()))?;
+        for skill in &self.skills {
+            map.serialize_entry(skill.skill.name(), skill)?;
+        }
+        for boss in &self.bosses {
+            map.serialize_entry(&boss.name, boss)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayerGains {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        // Every skill and boss is a `{"xp": .., "rank": .., ..}` object keyed by its name.
+        let mut fields = BTreeMap::<String, Value>::deserialize(deserializer)?;
+
+        let skills = Skill::ALL
+            .into_iter()
+            .map(|skill| {
+                let gain = fields.remove(skill.name()).unwrap_or_default();
+                let gain = match gain {
+                    Value::Null => SkillGain::default(),
+                    gain => SkillGain::deserialize(gain).map_err(D::Error::custom)?,
+                };
+                Ok(SkillGain { skill, ..gain })
+            })
+            .collect::<Result<_, D::Error>>()?;
+
+        let bosses = fields
+            .into_iter()
+            .filter(|(_, value)| value.get("kc").is_some())
+            .map(|(name, value)| {
+                let gain = BossGain::deserialize(value).map_err(D::Error::custom)?;
+                Ok(BossGain { name, ..gain })
+            })
+            .collect::<Result<_, D::Error>>()?;
+
+        Ok(Self { skills, bosses })
+    }
+}
+
+/// Best gain of a player in one metric over one [`Period`].
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Record {
+    #[serde(alias = "xp", alias = "kc")]
+    pub gained: i64,
+    /// When the record was set, if TempleOSRS knows.
+    #[serde(default, alias = "time")]
+    pub date: Option<Timestamp>,
+}
+
+/// Best day, week and month of a player in one metric.
+#[derive(Debug, PartialEq)]
+pub struct MetricRecords {
+    pub metric: Metric,
+    pub day: Option<Record>,
+    pub week: Option<Record>,
+    pub month: Option<Record>,
+}
+
+impl MetricRecords {
+    /// The record for `period`, `None` for periods TempleOSRS doesn't keep records for.
+    pub fn best(&self, period: Period) -> Option<&Record> {
+        match period {
+            Period::Day => self.day.as_ref(),
+            Period::Week => self.week.as_ref(),
+            Period::Month => self.month.as_ref(),
+            Period::Year | Period::Seconds(_) => None,
+        }
+    }
+}
+
+/// Personal bests of a player, as returned by `player_records.php`.
+#[derive(Debug, PartialEq)]
+pub struct PlayerRecords {
+    /// Skills in the order of [`Skill::ALL`], then bosses and activities sorted by name.
+    pub records: Vec<MetricRecords>,
+}
+
+impl PlayerRecords {
+    pub fn get(&self, metric: &Metric) -> Option<&MetricRecords> {
+        self.records
+            .iter()
+            .find(|records| &records.metric == metric)
+    }
+}
+
+impl Serialize for PlayerRecords {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Raw<'a> {
+            day: &'a Option<Record>,
+            week: &'a Option<Record>,
+            month: &'a Option<Record>,
+        }
+
+        let mut map = serializer.serialize_map(Some(self.records.len()))?;
+        for records in &self.records {
+            let raw = Raw {
+                day: &records.day,
+                week: &records.week,
+                month: &records.month,
+            };
+            map.serialize_entry(records.metric.key(), &raw)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayerRecords {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        struct Raw {
+            #[serde(default, alias = "Day")]
+            day: Option<Record>,
+            #[serde(default, alias = "Week")]
+            week: Option<Record>,
+            #[serde(default, alias = "Month")]
+            month: Option<Record>,
+        }
+
+        // Every metric is a `{"day": {..}, "week": {..}, "month": {..}}` object keyed by its name.
+        let mut fields = BTreeMap::<String, Value>::deserialize(deserializer)?;
+        let mut names: Vec<String> = Skill::ALL
+            .into_iter()
+            .map(Skill::name)
+            .filter(|name| fields.contains_key(*name))
+            .map(str::to_owned)
+            .collect();
+        names.extend(
+            fields
+                .keys()
+                .filter(|name| Skill::from_name(name).is_none())
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+
+        let records = names
+            .into_iter()
+            .filter_map(|name| {
+                let value = fields.remove(&name)?;
+                if !value.is_object() {
+                    return None;
+                }
+                let metric = Metric::from_name(&name);
+                Some(
+                    Raw::deserialize(value)
+                        .map(|raw| MetricRecords {
+                            metric,
+                            day: raw.day,
+                            week: raw.week,
+                            month: raw.month,
+                        })
+                        .map_err(D::Error::custom),
+                )
+            })
+            .collect::<Result<_, D::Error>>()?;
+
+        Ok(Self { records })
+    }
+}
+
+/// A boss tracked by TempleOSRS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Boss {
+    AbyssalSire,
+    AlchemicalHydra,
+    Artio,
+    BarrowsChests,
+    Bryophyta,
+    Callisto,
+    Calvarion,
+    Cerberus,
+    ChambersOfXeric,
+    ChambersOfXericChallengeMode,
+    ChaosElemental,
+    ChaosFanatic,
+    CommanderZilyana,
+    CorporealBeast,
+    CrazyArchaeologist,
+    DagannothPrime,
+    DagannothRex,
+    DagannothSupreme,
+    DerangedArchaeologist,
+    DukeSucellus,
+    GeneralGraardor,
+    GiantMole,
+    GrotesqueGuardians,
+    Hespori,
+    KalphiteQueen,
+    KingBlackDragon,
+    Kraken,
+    KreeArra,
+    KrilTsutsaroth,
+    Mimic,
+    Nex,
+    Nightmare,
+    PhosanisNightmare,
+    Obor,
+    PhantomMuspah,
+    Sarachnis,
+    Scorpia,
+    Skotizo,
+    Spindel,
+    Tempoross,
+    TheCorruptedGauntlet,
+    TheGauntlet,
+    TheLeviathan,
+    TheWhisperer,
+    TheatreOfBlood,
+    TheatreOfBloodHardMode,
+    ThermonuclearSmokeDevil,
+    TombsOfAmascut,
+    TombsOfAmascutExpert,
+    TzKalZuk,
+    TzTokJad,
+    Vardorvis,
+    Venenatis,
+    Vetion,
+    Vorkath,
+    Wintertodt,
+    Zalcano,
+    Zulrah,
+}
+
+impl Boss {
+    pub const ALL: [Boss; 58] = [
+        Boss::AbyssalSire,
+        Boss::AlchemicalHydra,
+        Boss::Artio,
+        Boss::BarrowsChests,
+        Boss::Bryophyta,
+        Boss::Callisto,
+        Boss::Calvarion,
+        Boss::Cerberus,
+        Boss::ChambersOfXeric,
+        Boss::ChambersOfXericChallengeMode,
+        Boss::ChaosElemental,
+        Boss::ChaosFanatic,
+        Boss::CommanderZilyana,
+        Boss::CorporealBeast,
+        Boss::CrazyArchaeologist,
+        Boss::DagannothPrime,
+        Boss::DagannothRex,
+        Boss::DagannothSupreme,
+        Boss::DerangedArchaeologist,
+        Boss::DukeSucellus,
+        Boss::GeneralGraardor,
+        Boss::GiantMole,
+        Boss::GrotesqueGuardians,
+        Boss::Hespori,
+        Boss::KalphiteQueen,
+        Boss::KingBlackDragon,
+        Boss::Kraken,
+        Boss::KreeArra,
+        Boss::KrilTsutsaroth,
+        Boss::Mimic,
+        Boss::Nex,
+        Boss::Nightmare,
+        Boss::PhosanisNightmare,
+        Boss::Obor,
+        Boss::PhantomMuspah,
+        Boss::Sarachnis,
+        Boss::Scorpia,
+        Boss::Skotizo,
+        Boss::Spindel,
+        Boss::Tempoross,
+        Boss::TheCorruptedGauntlet,
+        Boss::TheGauntlet,
+        Boss::TheLeviathan,
+        Boss::TheWhisperer,
+        Boss::TheatreOfBlood,
+        Boss::TheatreOfBloodHardMode,
+        Boss::ThermonuclearSmokeDevil,
+        Boss::TombsOfAmascut,
+        Boss::TombsOfAmascutExpert,
+        Boss::TzKalZuk,
+        Boss::TzTokJad,
+        Boss::Vardorvis,
+        Boss::Venenatis,
+        Boss::Vetion,
+        Boss::Vorkath,
+        Boss::Wintertodt,
+        Boss::Zalcano,
+        Boss::Zulrah,
+    ];
+
+    /// Name of the boss, as used in TempleOSRS responses.
+    pub fn name(self) -> &'static str {
+        match self {
+            Boss::AbyssalSire => "Abyssal Sire",
+            Boss::AlchemicalHydra => "Alchemical Hydra",
+            Boss::Artio => "Artio",
+            Boss::BarrowsChests => "Barrows Chests",
+            Boss::Bryophyta => "Bryophyta",
+            Boss::Callisto => "Callisto",
+            Boss::Calvarion => "Calvarion",
+            Boss::Cerberus => "Cerberus",
+            Boss::ChambersOfXeric => "Chambers of Xeric",
+            Boss::ChambersOfXericChallengeMode => "Chambers of Xeric Challenge Mode",
+            Boss::ChaosElemental => "Chaos Elemental",
+            Boss::ChaosFanatic => "Chaos Fanatic",
+            Boss::CommanderZilyana => "Commander Zilyana",
+            Boss::CorporealBeast => "Corporeal Beast",
+            Boss::CrazyArchaeologist => "Crazy Archaeologist",
+            Boss::DagannothPrime => "Dagannoth Prime",
+            Boss::DagannothRex => "Dagannoth Rex",
+            Boss::DagannothSupreme => "Dagannoth Supreme",
+            Boss::DerangedArchaeologist => "Deranged Archaeologist",
+            Boss::DukeSucellus => "Duke Sucellus",
+            Boss::GeneralGraardor => "General Graardor",
+            Boss::GiantMole => "Giant Mole",
+            Boss::GrotesqueGuardians => "Grotesque Guardians",
+            Boss::Hespori => "Hespori",
+            Boss::KalphiteQueen => "Kalphite Queen",
+            Boss::KingBlackDragon => "King Black Dragon",
+            Boss::Kraken => "Kraken",
+            Boss::KreeArra => "Kree'Arra",
+            Boss::KrilTsutsaroth => "K'ril Tsutsaroth",
+            Boss::Mimic => "Mimic",
+            Boss::Nex => "Nex",
+            Boss::Nightmare => "Nightmare",
+            Boss::PhosanisNightmare => "Phosani's Nightmare",
+            Boss::Obor => "Obor",
+            Boss::PhantomMuspah => "Phantom Muspah",
+            Boss::Sarachnis => "Sarachnis",
+            Boss::Scorpia => "Scorpia",
+            Boss::Skotizo => "Skotizo",
+            Boss::Spindel => "Spindel",
+            Boss::Tempoross => "Tempoross",
+            Boss::TheCorruptedGauntlet => "The Corrupted Gauntlet",
+            Boss::TheGauntlet => "The Gauntlet",
+            Boss::TheLeviathan => "The Leviathan",
+            Boss::TheWhisperer => "The Whisperer",
+            Boss::TheatreOfBlood => "Theatre of Blood",
+            Boss::TheatreOfBloodHardMode => "Theatre of Blood Challenge Mode",
+            Boss::ThermonuclearSmokeDevil => "Thermonuclear Smoke Devil",
+            Boss::TombsOfAmascut => "Tombs of Amascut",
+            Boss::TombsOfAmascutExpert => "Tombs of Amascut Expert",
+            Boss::TzKalZuk => "TzKal-Zuk",
+            Boss::TzTokJad => "TzTok-Jad",
+            Boss::Vardorvis => "Vardorvis",
+            Boss::Venenatis => "Venenatis",
+            Boss::Vetion => "Vet'ion",
+            Boss::Vorkath => "Vorkath",
+            Boss::Wintertodt => "Wintertodt",
+            Boss::Zalcano => "Zalcano",
+            Boss::Zulrah => "Zulrah",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|boss| boss.name() == name)
+    }
+}
+
+impl fmt::Display for Boss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A clue scroll tier or minigame tracked by TempleOSRS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Activity {
+    ClueAll,
+    ClueBeginner,
+    ClueEasy,
+    ClueMedium,
+    ClueHard,
+    ClueElite,
+    ClueMaster,
+    LastManStanding,
+    SoulWarsZeal,
+    BountyHunterHunter,
+    BountyHunterRogue,
+    PvpArena,
+    GuardiansOfTheRift,
+}
+
+impl Activity {
+    pub const ALL: [Activity; 13] = [
+        Activity::ClueAll,
+        Activity::ClueBeginner,
+        Activity::ClueEasy,
+        Activity::ClueMedium,
+        Activity::ClueHard,
+        Activity::ClueElite,
+        Activity::ClueMaster,
+        Activity::LastManStanding,
+        Activity::SoulWarsZeal,
+        Activity::BountyHunterHunter,
+        Activity::BountyHunterRogue,
+        Activity::PvpArena,
+        Activity::GuardiansOfTheRift,
+    ];
+
+    /// Key of the activity in TempleOSRS responses.
+    pub fn key(self) -> &'static str {
+        match self {
+            Activity::ClueAll => "Clue_all",
+            Activity::ClueBeginner => "Clue_beginner",
+            Activity::ClueEasy => "Clue_easy",
+            Activity::ClueMedium => "Clue_medium",
+            Activity::ClueHard => "Clue_hard",
+            Activity::ClueElite => "Clue_elite",
+            Activity::ClueMaster => "Clue_master",
+            Activity::LastManStanding => "LMS",
+            Activity::SoulWarsZeal => "Soul Wars Zeal",
+            Activity::BountyHunterHunter => "Bounty Hunter Hunter",
+            Activity::BountyHunterRogue => "Bounty Hunter Rogue",
+            Activity::PvpArena => "PvP Arena",
+            Activity::GuardiansOfTheRift => "Guardians of the Rift",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|activity| activity.key() == key)
+    }
+}
+
+impl fmt::Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Activity::ClueAll => "Clue scrolls (all)",
+            Activity::ClueBeginner => "Clue scrolls (beginner)",
+            Activity::ClueEasy => "Clue scrolls (easy)",
+            Activity::ClueMedium => "Clue scrolls (medium)",
+            Activity::ClueHard => "Clue scrolls (hard)",
+            Activity::ClueElite => "Clue scrolls (elite)",
+            Activity::ClueMaster => "Clue scrolls (master)",
+            Activity::LastManStanding => "Last Man Standing",
+            Activity::SoulWarsZeal => "Soul Wars Zeal",
+            Activity::BountyHunterHunter => "Bounty Hunter (hunter)",
+            Activity::BountyHunterRogue => "Bounty Hunter (rogue)",
+            Activity::PvpArena => "PvP Arena",
+            Activity::GuardiansOfTheRift => "Guardians of the Rift",
+        })
+    }
+}
+
+/// Boss kill counts of a player. Bosses the player isn't ranked in are left out.
+#[derive(Debug, PartialEq, Default)]
+pub struct Bosses {
+    pub kills: BTreeMap<Boss, u32>,
+    /// Efficient hours bossed.
+    pub ehb: f64,
+}
+
+impl Bosses {
+    pub fn get(&self, boss: Boss) -> Option<u32> {
+        self.kills.get(&boss).copied()
+    }
+}
+
+impl Serialize for Bosses {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.kills.len() + 1))?;
+        for (boss, kills) in &self.kills {
+            map.serialize_entry(boss.name(), kills)?;
+        }
+        map.serialize_entry("Ehb", &self.ehb)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Bosses {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let fields = HashMap::<String, Value>::deserialize(deserializer)?;
+        Ok(Self {
+            kills: fields
+                .iter()
+                .filter_map(|(key, value)| Some((Boss::from_name(key)?, count(value)?)))
+                .collect(),
+            ehb: fields
+                .get("Ehb")
+                .and_then(Value::as_f64)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Clue scroll and minigame scores of a player. Activities the player isn't
+/// ranked in are left out.
+#[derive(Debug, PartialEq, Default)]
+pub struct Activities {
+    pub scores: BTreeMap<Activity, u32>,
+}
+
+impl Activities {
+    pub fn get(&self, activity: Activity) -> Option<u32> {
+        self.scores.get(&activity).copied()
+    }
+}
+
+impl Serialize for Activities {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.scores.len()))?;
+        for (activity, score) in &self.scores {
+            map.serialize_entry(activity.key(), score)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Activities {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let fields = HashMap::<String, Value>::deserialize(deserializer)?;
+        Ok(Self {
+            scores: fields
+                .iter()
+                .filter_map(|(key, value)| Some((Activity::from_key(key)?, count(value)?)))
+                .collect(),
+        })
+    }
+}
+
+/// Unranked kill counts are reported as `null` or `-1`.
+fn count(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|value| u32::try_from(value).ok())
+}
+
+/// A datapoint of `player_datapoints.php` requested with `bosses=1`.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Default)]
+pub struct KillCounts {
+    #[serde(flatten)]
+    pub bosses: Bosses,
+    #[serde(flatten)]
+    pub activities: Activities,
+}
+
+/// Collection log of a player, as returned by `player_collection_log.php`.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct CollectionLog {
+    pub player: String,
+    #[serde(default)]
+    pub total_collections_finished: u32,
+    #[serde(default)]
+    pub total_collections_available: u32,
+    #[serde(default)]
+    pub last_changed: Option<Timestamp>,
+    /// Items per category, keyed by category name such as `abyssal_sire`.
+    /// Missing items are included with a count of zero.
+    pub items: BTreeMap<String, Vec<CollectionLogItem>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct CollectionLogItem {
+    pub id: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub count: u32,
+    /// When the item was first obtained, if known.
+    #[serde(default)]
+    pub date: Option<Timestamp>,
+}
+
+impl CollectionLogItem {
+    pub fn obtained(&self) -> bool {
+        self.count > 0
+    }
+}
+
+/// Summary of one collection log category, see [`CollectionLog::categories`].
+#[derive(Debug)]
+pub struct CollectionLogCategory<'a> {
+    pub key: &'a str,
+    pub items: &'a [CollectionLogItem],
+}
+
+impl CollectionLogCategory<'_> {
+    /// The category key with underscores replaced and words capitalized,
+    /// e.g. `abyssal_sire` becomes `Abyssal Sire`.
+    pub fn name(&self) -> String {
+        self.key
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_uppercase().chain(chars).collect()
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn obtained(&self) -> usize {
+        self.items.iter().filter(|item| item.obtained()).count()
+    }
+
+    pub fn total(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl CollectionLog {
+    pub fn categories(&self) -> impl Iterator<Item = CollectionLogCategory<'_>> {
+        self.items
+            .iter()
+            .map(|(key, items)| CollectionLogCategory { key, items })
+    }
+}
+
+/// What a competition, leaderboard or record is measured in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Metric {
+    Skill(Skill),
+    /// Efficient hours played.
+    Ehp,
+    Boss(Boss),
+    Activity(Activity),
+    /// A metric this version doesn't know about yet, by its name.
+    Unknown(String),
+}
+
+impl Metric {
+    pub fn from_name(name: &str) -> Self {
+        if let Some(skill) = Skill::from_name(name) {
+            Metric::Skill(skill)
+        } else if name == "Ehp" {
+            Metric::Ehp
+        } else if let Some(boss) = Boss::from_name(name) {
+            Metric::Boss(boss)
+        } else if let Some(activity) = Activity::from_key(name) {
+            Metric::Activity(activity)
+        } else {
+            Metric::Unknown(name.to_owned())
+        }
+    }
+
+    /// Name of the metric in TempleOSRS requests and responses.
+    pub fn key(&self) -> &str {
+        match self {
+            Metric::Skill(skill) => skill.name(),
+            Metric::Ehp => "Ehp",
+            Metric::Boss(boss) => boss.name(),
+            Metric::Activity(activity) => activity.key(),
+            Metric::Unknown(name) => name,
+        }
+    }
+
+    /// Whether the metric counts kills or completions rather than experience.
+    pub fn is_kill_count(&self) -> bool {
+        matches!(self, Metric::Boss(_) | Metric::Activity(_))
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::Skill(skill) => skill.fmt(f),
+            Metric::Ehp => f.write_str("EHP"),
+            Metric::Boss(boss) => boss.fmt(f),
+            Metric::Activity(activity) => activity.fmt(f),
+            Metric::Unknown(name) => f.write_str(name),
+        }
+    }
+}
+
+impl Serialize for Metric {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.key())
+    }
+}
+
+impl<'de> Deserialize<'de> for Metric {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self::from_name(&String::deserialize(deserializer)?))
+    }
+}
+
+/// A competition, as returned by `competition_info.php`.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct Competition {
+    pub info: CompetitionInfo,
+    #[serde(default)]
+    pub participants: Vec<CompetitionParticipant>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct CompetitionInfo {
+    pub id: u32,
+    pub name: String,
+    #[serde(rename = "skill")]
+    pub metric: Metric,
+    #[serde(rename = "start_date")]
+    pub start: Timestamp,
+    #[serde(rename = "end_date")]
+    pub end: Timestamp,
+}
+
+/// A participant of a [`Competition`]. Experience for skill competitions,
+/// kill count for boss and activity competitions.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct CompetitionParticipant {
+    #[serde(alias = "username")]
+    pub player: String,
+    #[serde(default)]
+    pub start_xp: u64,
+    #[serde(default)]
+    pub end_xp: u64,
+    #[serde(rename = "xp_gained", default)]
+    pub gained: i64,
+}
+
+impl CompetitionInfo {
+    pub fn has_started(&self) -> bool {
+        self.start.0 <= Utc::now()
+    }
+
+    pub fn has_ended(&self) -> bool {
+        self.end.0 <= Utc::now()
+    }
+}
+
+impl Competition {
+    /// Participants sorted by their gains, highest first.
+    pub fn standings(&self) -> Vec<&CompetitionParticipant> {
+        let mut standings: Vec<_> = self.participants.iter().collect();
+        standings.sort_by_key(|participant| std::cmp::Reverse(participant.gained));
+        standings
+    }
+}
+
+/// A TempleOSRS group (clan), as returned by `group_info.php`.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct Group {
+    pub info: GroupDetails,
+    #[serde(default)]
+    pub members: Vec<GroupMember>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct GroupDetails {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub member_count: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct GroupMember {
+    #[serde(alias = "username", alias = "Username")]
+    pub player: String,
+    #[serde(default, alias = "Ehp")]
+    pub ehp: f64,
+    #[serde(default, alias = "Last checked")]
+    pub last_checked: Option<Timestamp>,
+}
+
+/// Gains of one group member, as returned by `group_gains.php`.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct GroupMemberGain {
+    #[serde(alias = "username")]
+    pub player: String,
+    #[serde(rename = "xp_gained", default)]
+    pub gained: i64,
+}
+
+/// Filters for [`Client::skill_hiscores`](super::Client::skill_hiscores).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HiscoreFilters {
+    pub game_mode: Option<GameMode>,
+    /// Country code, e.g. `NO`.
+    pub country: Option<String>,
+    /// Page of the leaderboard, starting at 1.
+    pub page: u32,
+}
+
+impl Default for HiscoreFilters {
+    fn default() -> Self {
+        Self {
+            game_mode: None,
+            country: None,
+            page: 1,
+        }
+    }
+}
+
+/// A row of a skill leaderboard, as returned by `skill_hiscores.php`.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct HiscoreRow {
+    pub rank: u32,
+    #[serde(alias = "username")]
+    pub player: String,
+    #[serde(default)]
+    pub xp: u64,
+    #[serde(default)]
+    pub level: Option<u32>,
+    #[serde(default)]
+    pub ehp: f64,
+}
+
+/// A rename of a player, as returned by `player_names.php`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct NameChange {
+    pub old_name: String,
+    pub new_name: String,
+    #[serde(rename = "time_changed", alias = "date")]
+    pub changed_at: Timestamp,
+}
+
+/// Datapoints of a player across all of their names, see
+/// [`Client::merged_player_datapoints`](super::Client::merged_player_datapoints).
+#[derive(Debug, Default)]
+pub struct MergedTimeline {
+    pub datapoints: BTreeMap<Timestamp, Skills>,
+    /// Renames of the player, oldest first.
+    pub renames: Vec<NameChange>,
+}
+
+impl MergedTimeline {
+    /// Merges the datapoints of the current name with those of older names,
+    /// each paired with when the player renamed away from it, see
+    /// [`MergedTimeline::old_names`]. Datapoints of an old name after that
+    /// are dropped, since they belong to whoever took the name next. The
+    /// current name wins when two timelines share a timestamp.
+    pub fn merge(
+        current: BTreeMap<Timestamp, Skills>,
+        old: impl IntoIterator<Item = (Timestamp, BTreeMap<Timestamp, Skills>)>,
+        mut renames: Vec<NameChange>,
+    ) -> Self {
+        let mut datapoints = BTreeMap::new();
+        for (renamed_at, timeline) in old {
+            datapoints.extend(
+                timeline
+                    .into_iter()
+                    .filter(|(timestamp, _)| *timestamp <= renamed_at),
+            );
+        }
+        datapoints.extend(current);
+        renames.sort_by_key(|rename| rename.changed_at);
+        Self {
+            datapoints,
+            renames,
+        }
+    }
+
+    /// The distinct names the player has had before their current name, with
+    /// the last time they renamed away from each.
+    pub fn old_names(renames: &[NameChange], current: &str) -> Vec<(String, Timestamp)> {
+        let mut names: Vec<(String, Timestamp)> = Vec::new();
+        for rename in renames {
+            let name = &rename.old_name;
+            if name.eq_ignore_ascii_case(current) {
+                continue;
+            }
+            match names
+                .iter_mut()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name))
+            {
+                Some((_, renamed_at)) => *renamed_at = (*renamed_at).max(rename.changed_at),
+                None => names.push((name.clone(), rename.changed_at)),
+            }
+        }
+        names
+    }
+}
+
+/// Information and datapoints of one player, see
+/// [`Client::batch_player_data`](super::Client::batch_player_data).
+#[derive(Debug)]
+pub struct PlayerData {
+    pub information: PlayerInformation,
+    pub datapoints: BTreeMap<Timestamp, Skills>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use serde_json::json;
+
+    /// Decodes `json`, checks that encoding it again gives back the same
+    /// JSON, and that decoding that gives back the same value.
+    fn round_trip<T>(json: Value) -> T
+    where
+        T: Serialize + DeserializeOwned + PartialEq + fmt::Debug,
+    {
+        let value: T = serde_json::from_value(json.clone()).unwrap();
+        let encoded = serde_json::to_value(&value).unwrap();
+        assert_eq!(encoded, json);
+        assert_eq!(serde_json::from_value::<T>(encoded).unwrap(), value);
+        value
+    }
+
+    fn skills_json() -> Value {
+        let mut skills: serde_json::Map<_, _> = Skill::ALL
+            .into_iter()
+            .zip(1_u32..)
+            .map(|(skill, xp)| (skill.name().to_owned(), json!(xp * 1000)))
+            .collect();
+        skills.insert("Ehp".to_owned(), json!(12.5));
+        Value::Object(skills)
+    }
+
+    #[test]
+    fn timestamp() {
+        let timestamp: Timestamp = round_trip(json!("2023-08-10 12:34:56"));
+        assert_eq!(timestamp.0.to_rfc3339(), "2023-08-10T12:34:56+00:00");
+    }
+
+    #[test]
+    fn game_mode() {
+        for id in 0..=u8::MAX {
+            assert_eq!(round_trip::<GameMode>(json!(id)).id(), id);
+        }
+        assert_eq!(round_trip::<GameMode>(json!(3)), GameMode::HardcoreIronman);
+        assert_eq!(round_trip::<GameMode>(json!(42)), GameMode::Unknown(42));
+    }
+
+    #[test]
+    fn player_information() {
+        let information: PlayerInformation = round_trip(json!({
+            "Username": "Lynx Titan",
+            "Country": "US",
+            "Game mode": 0,
+            "fresh_start_account": 0,
+            "Cb-3": 0,
+            "F2p": 0,
+            "Banned": 0,
+            "Disqualified": 1,
+            "Clan preference": null,
+            "Last checked": "2023-08-10 12:34:56",
+            "Last changed": "2023-08-09 08:00:00",
+            "Last changed KC": null,
+            "Datapoint Cooldown": "00:14:59",
+        }));
+        assert_eq!(information.datapoint_cooldown, Duration::from_secs(899));
+        assert!(information.disqualified);
+    }
+
+    #[test]
+    fn skills() {
+        let skills: Skills = round_trip(skills_json());
+        assert_eq!(skills.get(Skill::Construction), 24_000);
+        assert!(skills.extra.is_empty());
+    }
+
+    #[test]
+    fn skills_with_unknown_metrics() {
+        let mut json = skills_json();
+        json["Sailing"] = json!(1234);
+        json["Ehb"] = json!(1.5);
+        let skills: Skills = round_trip(json);
+        assert_eq!(skills.extra("Sailing"), Some(1234.0));
+        assert_eq!(skills.extra("Ehb"), Some(1.5));
+    }
+
+    #[test]
+    fn player_stats() {
+        let mut stats = serde_json::Map::new();
+        stats.insert("date".to_owned(), json!("2023-08-10 12:34:56"));
+        for skill in Skill::ALL {
+            let name = skill.name();
+            stats.insert(name.to_owned(), json!(13_034_431));
+            stats.insert(format!("{name}_level"), json!(99));
+            stats.insert(format!("{name}_rank"), json!(1));
+            stats.insert(format!("{name}_ehp"), json!(0.5));
+        }
+        stats.insert("Ehp".to_owned(), json!(12.0));
+        stats.insert("Ehp_rank".to_owned(), json!(7));
+        let stats: PlayerStats = round_trip(Value::Object(stats));
+        assert_eq!(stats.skill(Skill::Slayer).unwrap().level, Some(99));
+    }
+
+    #[test]
+    fn player_gains() {
+        let mut gains: serde_json::Map<_, _> = Skill::ALL
+            .into_iter()
+            .map(|skill| {
+                let gain = json!({"xp": 100, "level": 0, "rank": -5, "ehp": 0.25});
+                (skill.name().to_owned(), gain)
+            })
+            .collect();
+        gains.insert(
+            "Zulrah".to_owned(),
+            json!({"kc": 20, "rank": -100, "ehb": 0.5}),
+        );
+        let gains: PlayerGains = round_trip(Value::Object(gains));
+        assert_eq!(gains.boss("Zulrah").unwrap().kc, 20);
+    }
+
+    #[test]
+    fn player_records() {
+        let records: PlayerRecords = round_trip(json!({
+            "Overall": {
+                "day": {"gained": 1_000_000, "date": "2023-08-10 12:34:56"},
+                "week": {"gained": 5_000_000, "date": null},
+                "month": null,
+            },
+            "Zulrah": {
+                "day": {"gained": 50, "date": "2023-08-10 12:34:56"},
+                "week": null,
+                "month": null,
+            },
+        }));
+        let zulrah = records.get(&Metric::Boss(Boss::Zulrah)).unwrap();
+        assert_eq!(zulrah.best(Period::Day).unwrap().gained, 50);
+    }
+
+    #[test]
+    fn kill_counts() {
+        let kill_counts: KillCounts = round_trip(json!({
+            "Zulrah": 100,
+            "Vorkath": 5,
+            "Ehb": 2.5,
+            "Clue_all": 10,
+        }));
+        assert_eq!(kill_counts.bosses.get(Boss::Vorkath), Some(5));
+        assert_eq!(kill_counts.activities.get(Activity::ClueAll), Some(10));
+    }
+
+    #[test]
+    fn collection_log() {
+        round_trip::<CollectionLog>(json!({
+            "player": "Lynx Titan",
+            "total_collections_finished": 1,
+            "total_collections_available": 2,
+            "last_changed": "2023-08-10 12:34:56",
+            "items": {
+                "zulrah": [
+                    {"id": 12921, "name": "Pet snakeling", "count": 1, "date": "2023-08-10 12:34:56"},
+                    {"id": 12922, "name": "Tanzanite fang", "count": 0, "date": null},
+                ],
+            },
+        }));
+    }
+
+    #[test]
+    fn competition() {
+        let competition: Competition = round_trip(json!({
+            "info": {
+                "id": 1,
+                "name": "SOTW",
+                "skill": "Slayer",
+                "start_date": "2023-08-01 00:00:00",
+                "end_date": "2023-08-08 00:00:00",
+            },
+            "participants": [
+                {"player": "Lynx Titan", "start_xp": 100, "end_xp": 250, "xp_gained": 150},
+            ],
+        }));
+        assert_eq!(competition.info.metric, Metric::Skill(Skill::Slayer));
+    }
+
+    #[test]
+    fn group() {
+        round_trip::<Group>(json!({
+            "info": {"id": 1, "name": "Clan", "member_count": 1},
+            "members": [
+                {"player": "Lynx Titan", "ehp": 1.5, "last_checked": "2023-08-10 12:34:56"},
+            ],
+        }));
+        round_trip::<Vec<GroupMemberGain>>(json!([
+            {"player": "Lynx Titan", "xp_gained": 1000},
+        ]));
+    }
+
+    #[test]
+    fn hiscore_rows() {
+        round_trip::<Vec<HiscoreRow>>(json!([
+            {"rank": 1, "player": "Lynx Titan", "xp": 200_000_000, "level": 99, "ehp": 1.5},
+            {"rank": 2, "player": "Someone", "xp": 0, "level": null, "ehp": 0.0},
+        ]));
+    }
+
+    #[test]
+    fn merged_timeline_drops_datapoints_after_rename() {
+        let at = |time: &str| serde_json::from_value::<Timestamp>(json!(time)).unwrap();
+        let skills = |overall: u64| {
+            let mut json = skills_json();
+            json["Overall"] = json!(overall);
+            serde_json::from_value::<Skills>(json).unwrap()
+        };
+        let renames = vec![NameChange {
+            old_name: "Old".to_owned(),
+            new_name: "New".to_owned(),
+            changed_at: at("2023-06-01 00:00:00"),
+        }];
+        let old_names = MergedTimeline::old_names(&renames, "New");
+        assert_eq!(old_names, [("Old".to_owned(), at("2023-06-01 00:00:00"))]);
+
+        let old = BTreeMap::from([
+            (at("2023-01-01 00:00:00"), skills(1)),
+            (at("2023-06-01 00:00:00"), skills(2)),
+            // Someone else took the name.
+            (at("2023-09-01 00:00:00"), skills(3)),
+        ]);
+        let current = BTreeMap::from([(at("2023-07-01 00:00:00"), skills(4))]);
+        let old = old_names
+            .into_iter()
+            .map(|(_, renamed_at)| renamed_at)
+            .zip([old]);
+        let timeline = MergedTimeline::merge(current, old, renames);
+        assert_eq!(
+            timeline.datapoints.keys().copied().collect::<Vec<_>>(),
+            [
+                at("2023-01-01 00:00:00"),
+                at("2023-06-01 00:00:00"),
+                at("2023-07-01 00:00:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn error_payload() {
+        use crate::api::error::ErrorPayload;
+
+        let payload: ErrorPayload = round_trip(json!({"Code": 402, "Message": "Player not found"}));
+        assert_eq!(payload.code, Some(402));
+        round_trip::<ErrorPayload>(json!({"Message": "Something went wrong"}));
+    }
+
+    #[test]
+    fn name_changes() {
+        round_trip::<Vec<NameChange>>(json!([
+            {"old_name": "Old", "new_name": "New", "time_changed": "2023-08-10 12:34:56"},
+        ]));
+    }
 }
@@ -0,0 +1,181 @@
+//! The experience table, and levels derived from experience.
+
+use super::types::{Skill, Skills};
+use std::sync::OnceLock;
+
+/// Experience at which a skill stops gaining experience.
+pub const MAX_XP: u64 = 200_000_000;
+/// Highest real level.
+pub const MAX_LEVEL: u32 = 99;
+/// Highest virtual level, the last one below [`MAX_XP`].
+pub const MAX_VIRTUAL_LEVEL: u32 = 126;
+
+/// Experience needed for every level, indexed by level. Index 0 is unused.
+fn table() -> &'static [u64; MAX_VIRTUAL_LEVEL as usize + 1] {
+    static TABLE: OnceLock<[u64; MAX_VIRTUAL_LEVEL as usize + 1]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0; MAX_VIRTUAL_LEVEL as usize + 1];
+        let mut points = 0.;
+        for level in 2..=MAX_VIRTUAL_LEVEL {
+            let previous = f64::from(level - 1);
+            points += (previous + 300. * 2_f64.powf(previous / 7.)).floor();
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let xp = (points / 4.).floor() as u64;
+            table[level as usize] = xp;
+        }
+        table
+    })
+}
+
+/// Experience needed to reach `level`, which is clamped to `1..=126`.
+pub fn xp_for_level(level: u32) -> u64 {
+    table()[level.clamp(1, MAX_VIRTUAL_LEVEL) as usize]
+}
+
+/// Level at `xp`, including virtual levels above 99.
+pub fn virtual_level_for_xp(xp: u64) -> u32 {
+    (2..=MAX_VIRTUAL_LEVEL)
+        .take_while(|&level| xp_for_level(level) <= xp)
+        .last()
+        .unwrap_or(1)
+}
+
+/// Level at `xp`, capped at 99.
+pub fn level_for_xp(xp: u64) -> u32 {
+    virtual_level_for_xp(xp).min(MAX_LEVEL)
+}
+
+/// Experience left until the next virtual level, or until [`MAX_XP`] at level 126.
+pub fn xp_to_next_level(xp: u64) -> u64 {
+    let level = virtual_level_for_xp(xp);
+    let target = if level < MAX_VIRTUAL_LEVEL {
+        xp_for_level(level + 1)
+    } else {
+        MAX_XP
+    };
+    target.saturating_sub(xp)
+}
+
+/// Experience left until level 99, zero once it's reached.
+pub fn xp_to_99(xp: u64) -> u64 {
+    xp_for_level(MAX_LEVEL).saturating_sub(xp)
+}
+
+impl Skills {
+    /// Level in `skill`, capped at 99. The level of [`Skill::Overall`] is the
+    /// [total level](Skills::total_level).
+    pub fn level(&self, skill: Skill) -> u32 {
+        match skill {
+            Skill::Overall => self.total_level(),
+            skill => level_for_xp(self.get(skill)),
+        }
+    }
+
+    /// Level in `skill`, including virtual levels above 99. The level of
+    /// [`Skill::Overall`] is the sum of the virtual levels.
+    pub fn virtual_level(&self, skill: Skill) -> u32 {
+        match skill {
+            Skill::Overall => Skill::ALL
+                .into_iter()
+                .filter(|&skill| skill != Skill::Overall)
+                .map(|skill| self.virtual_level(skill))
+                .sum(),
+            skill => virtual_level_for_xp(self.get(skill)),
+        }
+    }
+
+    pub fn total_level(&self) -> u32 {
+        Skill::ALL
+            .into_iter()
+            .filter(|&skill| skill != Skill::Overall)
+            .map(|skill| self.level(skill))
+            .sum()
+    }
+
+    pub fn combat_level(&self) -> u32 {
+        let level = |skill| f64::from(self.level(skill));
+        let base = 0.25
+            * (level(Skill::Defence)
+                + level(Skill::Hitpoints)
+                + (level(Skill::Prayer) / 2.).floor());
+        let melee = 0.325 * (level(Skill::Attack) + level(Skill::Strength));
+        let ranged = 0.325 * (level(Skill::Ranged) * 3. / 2.).floor();
+        let magic = 0.325 * (level(Skill::Magic) * 3. / 2.).floor();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let combat = (base + melee.max(ranged).max(magic)).floor() as u32;
+        combat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Map, Value};
+
+    /// Skills with `xp` in every skill but Hitpoints, which has `hitpoints`.
+    fn skills(xp: u64, hitpoints: u64) -> Skills {
+        let skills: Map<String, Value> = Skill::ALL
+            .into_iter()
+            .map(|skill| {
+                let xp = match skill {
+                    Skill::Overall => 0,
+                    Skill::Hitpoints => hitpoints,
+                    _ => xp,
+                };
+                (skill.name().to_owned(), json!(xp))
+            })
+            .chain([("Ehp".to_owned(), json!(0.0))])
+            .collect();
+        serde_json::from_value(Value::Object(skills)).unwrap()
+    }
+
+    #[test]
+    fn xp_table() {
+        assert_eq!(xp_for_level(1), 0);
+        assert_eq!(xp_for_level(2), 83);
+        assert_eq!(xp_for_level(92), 6_517_253);
+        assert_eq!(xp_for_level(99), 13_034_431);
+        assert_eq!(xp_for_level(126), 188_884_740);
+        // Out of range levels are clamped.
+        assert_eq!(xp_for_level(0), 0);
+        assert_eq!(xp_for_level(200), 188_884_740);
+    }
+
+    #[test]
+    fn levels() {
+        assert_eq!(level_for_xp(0), 1);
+        assert_eq!(level_for_xp(82), 1);
+        assert_eq!(level_for_xp(83), 2);
+        assert_eq!(level_for_xp(13_034_430), 98);
+        assert_eq!(level_for_xp(13_034_431), 99);
+        assert_eq!(level_for_xp(MAX_XP), 99);
+        assert_eq!(virtual_level_for_xp(14_391_160), 100);
+        assert_eq!(virtual_level_for_xp(MAX_XP), 126);
+    }
+
+    #[test]
+    fn xp_left() {
+        assert_eq!(xp_to_next_level(0), 83);
+        assert_eq!(xp_to_next_level(13_034_431), 14_391_160 - 13_034_431);
+        assert_eq!(xp_to_next_level(188_884_740), MAX_XP - 188_884_740);
+        assert_eq!(xp_to_next_level(MAX_XP), 0);
+        assert_eq!(xp_to_99(0), 13_034_431);
+        assert_eq!(xp_to_99(MAX_XP), 0);
+    }
+
+    #[test]
+    fn total_and_combat_level() {
+        let maxed = skills(xp_for_level(99), xp_for_level(99));
+        assert_eq!(maxed.total_level(), 2277);
+        assert_eq!(maxed.level(Skill::Overall), 2277);
+        assert_eq!(maxed.combat_level(), 126);
+
+        let new = skills(0, xp_for_level(10));
+        assert_eq!(new.total_level(), 32);
+        assert_eq!(new.combat_level(), 3);
+
+        let virtual_levels = skills(MAX_XP, MAX_XP);
+        assert_eq!(virtual_levels.total_level(), 2277);
+        assert_eq!(virtual_levels.virtual_level(Skill::Overall), 23 * 126);
+    }
+}
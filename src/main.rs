@@ -1,6 +1,7 @@
 #![allow(clippy::missing_errors_doc)]
 
 use anyhow::Result;
+use api::{cache::Cache, rate_limit::RateLimit, Client};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
@@ -8,18 +9,30 @@ use crossterm::{
 };
 use ratatui::prelude::*;
 use std::io;
+use store::Store;
 use tui::{run_app, App};
 pub mod api;
+pub mod store;
 pub mod tui;
 
 fn main() -> Result<()> {
+    // `--offline` only shows responses cached by earlier runs.
+    let offline = std::env::args().skip(1).any(|arg| arg == "--offline");
+    let client = Client::builder()
+        .cache(Cache::new(Cache::default_dir()).offline(offline))
+        .rate_limit(RateLimit::per_second(2).burst(10))
+        .build()?;
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let app = App::new("Posemann".to_string());
+    // Without the history everything is fetched from TempleOSRS every time.
+    let store = Store::open(Store::default_path()).ok();
+
+    let app = App::new("Posemann".to_string(), client, store);
     let _res = run_app(&mut terminal, app);
 
     disable_raw_mode()?;
@@ -0,0 +1,287 @@
+//! Local SQLite history of player datapoints, see [`Store`].
+
+use crate::api::types::{Skills, TimeRange, Timestamp};
+use chrono::{TimeZone, Utc};
+use rusqlite::{params, types::Value as SqlValue, Connection, OptionalExtension};
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+pub type Result<T, E = StoreError> = std::result::Result<T, E>;
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("stored datapoint at {timestamp} is invalid: {source}")]
+    Decode {
+        timestamp: i64,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS players (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE COLLATE NOCASE
+    );
+    CREATE TABLE IF NOT EXISTS datapoints (
+        player_id INTEGER NOT NULL REFERENCES players (id),
+        timestamp INTEGER NOT NULL,
+        skill TEXT NOT NULL,
+        -- No type, so that whole floats aren't turned into integers.
+        value NOT NULL,
+        PRIMARY KEY (player_id, timestamp, skill)
+    );
+";
+
+/// Datapoints of every player that has been looked at, kept on disk so the
+/// history stays available even if TempleOSRS prunes it, and so that only
+/// new datapoints have to be downloaded.
+///
+/// Every datapoint is stored as one row per skill, timestamps as seconds
+/// since the Unix epoch.
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            // Opening the database reports a clearer error than this would.
+            let _ = std::fs::create_dir_all(parent);
+        }
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// `temple-osrs/history.sqlite3` in the user's data directory, or in the
+    /// current directory on platforms without one.
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_default()
+            .join(env!("CARGO_PKG_NAME"))
+            .join("history.sqlite3")
+    }
+
+    /// Timestamp of the newest stored datapoint of the player.
+    pub fn latest(&self, player: &str) -> Result<Option<Timestamp>> {
+        let latest: Option<i64> = self
+            .connection
+            .query_row(
+                "SELECT MAX(timestamp) FROM datapoints
+                 JOIN players ON players.id = datapoints.player_id
+                 WHERE players.name = ?1",
+                [player],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(latest.and_then(timestamp))
+    }
+
+    /// Every stored datapoint of the player.
+    pub fn datapoints(&self, player: &str) -> Result<BTreeMap<Timestamp, Skills>> {
+        let mut statement = self.connection.prepare(
+            "SELECT timestamp, skill, value FROM datapoints
+             JOIN players ON players.id = datapoints.player_id
+             WHERE players.name = ?1",
+        )?;
+        let mut rows = statement.query([player])?;
+        let mut fields = BTreeMap::<i64, Map<String, Value>>::new();
+        while let Some(row) = rows.next()? {
+            let value = match row.get(2)? {
+                SqlValue::Integer(value) => Value::from(value),
+                SqlValue::Real(value) => Number::from_f64(value).map_or(Value::Null, Value::from),
+                _ => Value::Null,
+            };
+            fields
+                .entry(row.get(0)?)
+                .or_default()
+                .insert(row.get(1)?, value);
+        }
+
+        fields
+            .into_iter()
+            .filter_map(|(seconds, fields)| Some((seconds, timestamp(seconds)?, fields)))
+            .map(|(seconds, timestamp, fields)| {
+                let skills = Skills::deserialize(Value::Object(fields)).map_err(|source| {
+                    StoreError::Decode {
+                        timestamp: seconds,
+                        source,
+                    }
+                })?;
+                Ok((timestamp, skills))
+            })
+            .collect()
+    }
+
+    /// Stores datapoints of the player, replacing stored ones with the same timestamp.
+    pub fn insert(&mut self, player: &str, datapoints: &BTreeMap<Timestamp, Skills>) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO players (name) VALUES (?1) ON CONFLICT (name) DO NOTHING",
+            [player],
+        )?;
+        let player_id: i64 =
+            transaction.query_row("SELECT id FROM players WHERE name = ?1", [player], |row| {
+                row.get(0)
+            })?;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR REPLACE INTO datapoints (player_id, timestamp, skill, value)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (timestamp, skills) in datapoints {
+                let Ok(Value::Object(fields)) = serde_json::to_value(skills) else {
+                    continue;
+                };
+                for (skill, value) in fields {
+                    let value = match value.as_i64() {
+                        Some(value) => SqlValue::Integer(value),
+                        None => match value.as_f64() {
+                            Some(value) => SqlValue::Real(value),
+                            None => continue,
+                        },
+                    };
+                    statement.execute(params![player_id, timestamp.0.timestamp(), skill, value])?;
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// The datapoints of the player that still have to be downloaded: those
+    /// newer than the newest stored one, or all of them the first time.
+    pub fn sync_range(&self, player: &str) -> Result<TimeRange> {
+        Ok(match self.latest(player)? {
+            Some(start) => TimeRange::Between {
+                start,
+                end: Timestamp(Utc::now()),
+            },
+            None => TimeRange::AllTime,
+        })
+    }
+}
+
+fn timestamp(seconds: i64) -> Option<Timestamp> {
+    Utc.timestamp_opt(seconds, 0).single().map(Timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::Skill;
+    use serde_json::json;
+
+    fn at(seconds: i64) -> Timestamp {
+        timestamp(seconds).unwrap()
+    }
+
+    /// Skills with `xp` in every skill, plus the given unknown metrics.
+    fn skills(xp: u64, extra: Value) -> Skills {
+        let mut fields: Map<String, Value> = Skill::ALL
+            .into_iter()
+            .map(|skill| (skill.name().to_owned(), json!(xp)))
+            .collect();
+        fields.insert("Ehp".to_owned(), json!(1.5));
+        if let Value::Object(extra) = extra {
+            fields.extend(extra);
+        }
+        Skills::deserialize(Value::Object(fields)).unwrap()
+    }
+
+    #[test]
+    fn reads_back_inserted_datapoints() {
+        let mut store = Store::open_in_memory().unwrap();
+        let datapoints = BTreeMap::from([
+            (at(1_000), skills(100, json!({}))),
+            (at(2_000), skills(200, json!({"Sailing": 1234, "Ehb": 2.5}))),
+        ]);
+        store.insert("Lynx Titan", &datapoints).unwrap();
+
+        assert_eq!(store.datapoints("Lynx Titan").unwrap(), datapoints);
+        // Names are case insensitive.
+        assert_eq!(store.datapoints("lynx titan").unwrap(), datapoints);
+        assert!(store.datapoints("Zezima").unwrap().is_empty());
+    }
+
+    #[test]
+    fn latest_datapoint() {
+        let mut store = Store::open_in_memory().unwrap();
+        assert_eq!(store.latest("Lynx Titan").unwrap(), None);
+        store
+            .insert(
+                "Lynx Titan",
+                &BTreeMap::from([
+                    (at(2_000), skills(200, json!({}))),
+                    (at(1_000), skills(100, json!({}))),
+                ]),
+            )
+            .unwrap();
+        assert_eq!(store.latest("Lynx Titan").unwrap(), Some(at(2_000)));
+        assert_eq!(store.latest("Zezima").unwrap(), None);
+    }
+
+    #[test]
+    fn sync_range_starts_at_latest_datapoint() {
+        let mut store = Store::open_in_memory().unwrap();
+        assert_eq!(store.sync_range("Lynx Titan").unwrap(), TimeRange::AllTime);
+        store
+            .insert(
+                "Lynx Titan",
+                &BTreeMap::from([(at(1_000), skills(100, json!({})))]),
+            )
+            .unwrap();
+        match store.sync_range("Lynx Titan").unwrap() {
+            TimeRange::Between { start, end } => {
+                assert_eq!(start, at(1_000));
+                assert!(end > start);
+            }
+            range => panic!("expected a range between two timestamps, got {range:?}"),
+        }
+    }
+
+    #[test]
+    fn keeps_whole_floats_as_floats() {
+        let mut store = Store::open_in_memory().unwrap();
+        let mut fields =
+            serde_json::to_value(skills(100, json!({"Ehb": 2.0, "Sailing": 2}))).unwrap();
+        fields["Ehp"] = json!(12.0);
+        let datapoints =
+            BTreeMap::from([(at(1_000), Skills::deserialize(fields.clone()).unwrap())]);
+        store.insert("Lynx Titan", &datapoints).unwrap();
+
+        let stored = store.datapoints("Lynx Titan").unwrap();
+        assert_eq!(serde_json::to_value(&stored[&at(1_000)]).unwrap(), fields);
+    }
+
+    #[test]
+    fn replaces_datapoints_with_the_same_timestamp() {
+        let mut store = Store::open_in_memory().unwrap();
+        store
+            .insert(
+                "Lynx Titan",
+                &BTreeMap::from([(at(1_000), skills(100, json!({"Sailing": 1})))]),
+            )
+            .unwrap();
+        let replaced = BTreeMap::from([(at(1_000), skills(150, json!({"Sailing": 2})))]);
+        store.insert("Lynx Titan", &replaced).unwrap();
+        assert_eq!(store.datapoints("Lynx Titan").unwrap(), replaced);
+    }
+}
@@ -1,10 +1,31 @@
 use crate::api::{
-    types::{Skills, Timestamp},
-    Client,
+    types::{
+        Activity, Boss, CollectionLog, Competition, GameMode, Group, GroupMemberGain,
+        HiscoreFilters, HiscoreRow, KillCounts, MergedTimeline, Metric, NameChange, Period,
+        PlayerGains, PlayerInformation, PlayerRecords, PlayerStats, Skill, Skills, TimeRange,
+        Timestamp,
+    },
+    ApiError, Client,
 };
+use crate::store::Store;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use num_format::{Locale, ToFormattedString};
 use ratatui::{prelude::*, widgets::ListState, Frame, Terminal};
-use std::{collections::BTreeMap, io};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often the competition leaderboard is refreshed while it's shown.
+const COMPETITION_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// Period of the gains shown next to the personal bests.
+const RECORDS_GAINS_PERIOD: Period = Period::Week;
 
 pub struct StatefulList {
     pub state: ListState,
@@ -20,6 +41,9 @@ impl StatefulList {
     }
 
     pub fn next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i >= self.items.len() - 1 {
@@ -34,6 +58,9 @@ impl StatefulList {
     }
 
     pub fn previous(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
@@ -56,10 +83,233 @@ pub enum InputMode {
     Editing,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Chart,
+    Stats,
+    Records,
+    CollectionLog,
+    Competition,
+    Clan,
+    Leaderboard,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 7] = [
+        Tab::Chart,
+        Tab::Stats,
+        Tab::Records,
+        Tab::CollectionLog,
+        Tab::Competition,
+        Tab::Clan,
+        Tab::Leaderboard,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Chart => "Chart",
+            Tab::Stats => "Current stats",
+            Tab::Records => "Records",
+            Tab::CollectionLog => "Collection log",
+            Tab::Competition => "Competition",
+            Tab::Clan => "Clan",
+            Tab::Leaderboard => "Leaderboard",
+        }
+    }
+
+    pub fn input_target(self) -> InputTarget {
+        match self {
+            Tab::Chart | Tab::Stats | Tab::Records | Tab::CollectionLog => InputTarget::Username,
+            Tab::Competition => InputTarget::CompetitionId,
+            Tab::Clan => InputTarget::GroupId,
+            Tab::Leaderboard => InputTarget::Country,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&tab| tab == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// What the textbox edits, depending on the current [`Tab`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InputTarget {
+    Username,
+    CompetitionId,
+    GroupId,
+    Country,
+}
+
+impl InputTarget {
+    pub fn title(self) -> &'static str {
+        match self {
+            InputTarget::Username => "Username",
+            InputTarget::CompetitionId => "Competition ID",
+            InputTarget::GroupId => "Group ID",
+            InputTarget::Country => "Country",
+        }
+    }
+}
+
+/// What the selected entry of [`App::skills`] refers to.
+#[derive(Clone, Copy)]
+pub enum Selection {
+    Skill(Skill),
+    Boss(Boss),
+    Activity(Activity),
+    /// Index into [`App::extra_metrics`].
+    Extra(usize),
+}
+
+/// Results of requests made on a background thread by [`App::spawn`].
+pub enum Message {
+    Information {
+        username: String,
+        result: Result<PlayerInformation, ApiError>,
+    },
+    DatapointAdded {
+        username: String,
+        result: Result<(), ApiError>,
+    },
+    Datapoints {
+        username: String,
+        result: Result<MergedTimeline, ApiError>,
+    },
+    KillCounts {
+        username: String,
+        result: Result<BTreeMap<Timestamp, KillCounts>, ApiError>,
+    },
+    Stats {
+        username: String,
+        result: Result<PlayerStats, ApiError>,
+    },
+    Records {
+        username: String,
+        result: Result<PlayerRecords, ApiError>,
+    },
+    Gains {
+        username: String,
+        result: Result<PlayerGains, ApiError>,
+    },
+    CollectionLog {
+        username: String,
+        result: Result<CollectionLog, ApiError>,
+    },
+    Competition {
+        id: u32,
+        result: Result<Competition, ApiError>,
+    },
+    Group {
+        id: u32,
+        result: Result<Group, ApiError>,
+    },
+    GroupGains {
+        id: u32,
+        result: Result<Vec<GroupMemberGain>, ApiError>,
+    },
+    Leaderboard {
+        skill: Skill,
+        filters: HiscoreFilters,
+        result: Result<Vec<HiscoreRow>, ApiError>,
+    },
+}
+
+/// Syncs the datapoints of the player into the store, then reads them back.
+/// Stored datapoints are shown even if the sync fails, e.g. when offline or
+/// when TempleOSRS no longer has the player.
+///
+/// The store is only locked around reading and writing it, not while
+/// downloading, so other players can be loaded in the meantime.
+fn load_history(
+    client: &Client,
+    store: &Mutex<Store>,
+    username: &str,
+) -> Result<MergedTimeline, ApiError> {
+    let lock = || store.lock().expect("the store is not poisoned");
+    let Ok(range) = lock().sync_range(username) else {
+        // The store itself failed, so go without it.
+        return client.merged_player_datapoints(username, TimeRange::AllTime);
+    };
+    match client.merged_player_datapoints(username, range) {
+        Ok(timeline) => {
+            let mut store = lock();
+            let stored = store
+                .insert(username, &timeline.datapoints)
+                .and_then(|()| store.datapoints(username));
+            drop(store);
+            match stored {
+                Ok(datapoints) => Ok(MergedTimeline {
+                    datapoints,
+                    renames: timeline.renames,
+                }),
+                Err(_) if range == TimeRange::AllTime => Ok(timeline),
+                Err(_) => client.merged_player_datapoints(username, TimeRange::AllTime),
+            }
+        }
+        Err(error) => match lock().datapoints(username) {
+            Ok(datapoints) if !datapoints.is_empty() => Ok(MergedTimeline {
+                datapoints,
+                renames: Vec::new(),
+            }),
+            _ => Err(error),
+        },
+    }
+}
+
 pub struct App {
-    pub client: Client,
-    pub dataset: Option<BTreeMap<Timestamp, Skills>>,
+    pub client: Arc<Client>,
+    /// Local history the datapoints are synced into and read from, if it
+    /// could be opened.
+    store: Option<Arc<Mutex<Store>>>,
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+    /// `None` while the player information is being fetched.
+    pub information: Option<Result<PlayerInformation, ApiError>>,
+    /// When the datapoint cooldown of the player runs out.
+    next_update_at: Option<Instant>,
+    /// Outcome of the last update requested with [`App::request_update`].
+    pub update_status: Option<String>,
+    /// `None` while the datapoints are being fetched.
+    pub dataset: Option<Result<BTreeMap<Timestamp, Skills>, ApiError>>,
+    /// Renames of the player, flagged on the experience chart.
+    pub renames: Vec<NameChange>,
+    /// `None` while the boss and activity datapoints are being fetched.
+    pub kill_counts: Option<Result<BTreeMap<Timestamp, KillCounts>, ApiError>>,
+    /// `None` while the current stats are being fetched.
+    pub stats: Option<Result<PlayerStats, ApiError>>,
+    /// `None` while the personal bests are being fetched.
+    pub records: Option<Result<PlayerRecords, ApiError>>,
+    /// Gains over [`RECORDS_GAINS_PERIOD`], shown next to the records.
+    pub gains: Option<Result<PlayerGains, ApiError>>,
+    /// `None` while the collection log is being fetched.
+    pub collection_log: Option<Result<CollectionLog, ApiError>>,
+    pub collection_log_categories: StatefulList,
+    /// Items of the category selected in `collection_log_categories`.
+    pub collection_log_items: StatefulList,
+    /// Whether Up/Down move through the items instead of the categories.
+    pub collection_log_items_focused: bool,
+    pub competition_id: Option<u32>,
+    /// `None` while the competition is being fetched, or if no ID was entered.
+    pub competition: Option<Result<Competition, ApiError>>,
+    competition_fetched_at: Option<Instant>,
+    pub group_id: Option<u32>,
+    /// `None` while the group is being fetched, or if no ID was entered.
+    pub group: Option<Result<Group, ApiError>>,
+    /// Overall experience gained by every member this week.
+    pub group_gains: Option<Result<Vec<GroupMemberGain>, ApiError>>,
+    /// Skill of the shown leaderboard, `None` until the leaderboard tab is
+    /// first opened.
+    pub leaderboard_skill: Option<Skill>,
+    pub leaderboard_filters: HiscoreFilters,
+    /// `None` while the leaderboard is being fetched.
+    pub leaderboard: Option<Result<Vec<HiscoreRow>, ApiError>>,
+    pub leaderboard_rows: StatefulList,
+    pub tab: Tab,
     pub skills: StatefulList,
+    /// Metrics in the datapoints that aren't known [`Skill`]s, listed after
+    /// the activities.
+    pub extra_metrics: Vec<String>,
     pub cursor_position: usize,
     pub username: String,
     pub input: String,
@@ -67,100 +317,547 @@ pub struct App {
 }
 
 impl App {
-    pub fn new(username: String) -> Self {
-        let client = Client::new();
-        let dataset = client.player_datapoints(&username, 1_000_000_000).ok();
+    pub fn new(username: String, client: Client, store: Option<Store>) -> Self {
+        let (sender, receiver) = mpsc::channel();
         let mut skills = StatefulList::with_items(
-            [
-                "Overall",
-                "Attack",
-                "Defence",
-                "Strength",
-                "Hitpoints",
-                "Ranged",
-                "Prayer",
-                "Magic",
-                "Cooking",
-                "Woodcutting",
-                "Fletching",
-                "Fishing",
-                "Firemaking",
-                "Crafting",
-                "Smithing",
-                "Mining",
-                "Herblore",
-                "Agility",
-                "Thieving",
-                "Slayer",
-                "Farming",
-                "Runecraft",
-                "Hunter",
-                "Construction",
-                // "Ehp",
-            ]
-            .into_iter()
-            .map(std::borrow::ToOwned::to_owned)
-            .collect(),
+            Skill::ALL
+                .into_iter()
+                .map(|skill| skill.to_string())
+                .chain(Boss::ALL.into_iter().map(|boss| boss.to_string()))
+                .chain(
+                    Activity::ALL
+                        .into_iter()
+                        .map(|activity| activity.to_string()),
+                )
+                .collect(),
         );
         skills.state.select(Some(0));
-        Self {
-            client,
-            dataset,
+        let mut app = Self {
+            client: Arc::new(client),
+            store: store.map(|store| Arc::new(Mutex::new(store))),
+            sender,
+            receiver,
+            information: None,
+            next_update_at: None,
+            update_status: None,
+            dataset: None,
+            renames: Vec::new(),
+            kill_counts: None,
+            stats: None,
+            records: None,
+            gains: None,
+            collection_log: None,
+            collection_log_categories: StatefulList::with_items(Vec::new()),
+            collection_log_items: StatefulList::with_items(Vec::new()),
+            collection_log_items_focused: false,
+            competition_id: None,
+            competition: None,
+            competition_fetched_at: None,
+            group_id: None,
+            group: None,
+            group_gains: None,
+            leaderboard_skill: None,
+            leaderboard_filters: HiscoreFilters::default(),
+            leaderboard: None,
+            leaderboard_rows: StatefulList::with_items(Vec::new()),
+            tab: Tab::Chart,
             skills,
+            extra_metrics: Vec::new(),
             cursor_position: username.len(),
             input: username.clone(),
             username,
             input_mode: InputMode::Normal,
+        };
+        app.fetch_player();
+        app
+    }
+
+    /// Runs `job` on a background thread, so that slow requests don't block
+    /// the event loop. The returned [`Message`] is handled in [`App::receive`].
+    pub fn spawn<F>(&self, job: F)
+    where
+        F: FnOnce(&Client) -> Message + Send + 'static,
+    {
+        let client = Arc::clone(&self.client);
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            // The receiver only goes away when the app is shutting down.
+            let _ = sender.send(job(&client));
+        });
+    }
+
+    /// Handles all messages from finished background requests.
+    pub fn receive(&mut self) {
+        while let Ok(message) = self.receiver.try_recv() {
+            match message {
+                Message::Information { username, result } => {
+                    if username == self.username {
+                        self.next_update_at = result
+                            .as_ref()
+                            .ok()
+                            .map(|information| Instant::now() + information.datapoint_cooldown);
+                        self.information = Some(result);
+                    }
+                }
+                Message::DatapointAdded { username, result } => {
+                    if username == self.username {
+                        match result {
+                            Ok(()) => {
+                                self.update_status = Some("update requested".to_owned());
+                                self.fetch_player();
+                            }
+                            Err(error) => {
+                                self.update_status =
+                                    Some(format!("update failed: {}", error.kind()));
+                            }
+                        }
+                    }
+                }
+                Message::Datapoints { username, result } => {
+                    // Ignore responses for a username that has since been replaced.
+                    if username == self.username {
+                        self.dataset = Some(result.map(|timeline| {
+                            self.renames = timeline.renames;
+                            timeline.datapoints
+                        }));
+                        self.refresh_extra_metrics();
+                    }
+                }
+                Message::KillCounts { username, result } => {
+                    if username == self.username {
+                        self.kill_counts = Some(result);
+                    }
+                }
+                Message::Stats { username, result } => {
+                    if username == self.username {
+                        self.stats = Some(result);
+                    }
+                }
+                Message::Records { username, result } => {
+                    if username == self.username {
+                        self.records = Some(result);
+                    }
+                }
+                Message::Gains { username, result } => {
+                    if username == self.username {
+                        self.gains = Some(result);
+                    }
+                }
+                Message::CollectionLog { username, result } => {
+                    if username == self.username {
+                        self.collection_log = Some(result);
+                        self.reset_collection_log_lists();
+                    }
+                }
+                Message::Competition { id, result } => {
+                    if Some(id) == self.competition_id {
+                        self.competition = Some(result);
+                    }
+                }
+                Message::Group { id, result } => {
+                    if Some(id) == self.group_id {
+                        self.group = Some(result);
+                    }
+                }
+                Message::GroupGains { id, result } => {
+                    if Some(id) == self.group_id {
+                        self.group_gains = Some(result);
+                    }
+                }
+                Message::Leaderboard {
+                    skill,
+                    filters,
+                    result,
+                } => {
+                    if Some(skill) == self.leaderboard_skill && filters == self.leaderboard_filters
+                    {
+                        let rows = result.as_ref().map_or_else(
+                            |_| Vec::new(),
+                            |rows| {
+                                rows.iter()
+                                    .map(|row| {
+                                        format!(
+                                            "{:>7}  {:<13} {:>14}",
+                                            row.rank,
+                                            row.player,
+                                            row.xp.to_formatted_string(&Locale::en)
+                                        )
+                                    })
+                                    .collect()
+                            },
+                        );
+                        self.leaderboard_rows = StatefulList::with_items(rows);
+                        self.leaderboard_rows.next();
+                        self.leaderboard = Some(result);
+                    }
+                }
+            }
         }
     }
 
-    #[allow(
-        clippy::cast_precision_loss,
-        clippy::cast_lossless,
-        clippy::cast_possible_truncation,
-        clippy::cast_sign_loss
-    )]
-    pub fn get_data(&self) -> Option<Vec<(f64, f64)>> {
-        let selected = self.skills.state.selected().expect("a selected option");
-        let dataset = self.dataset.as_ref()?;
-        Some(
-            dataset
-                .iter()
-                .map(|(k, v)| {
-                    (
-                        k.0.timestamp() as f64,
-                        match selected {
-                            0 => v.overall,
-                            1 => v.attack as u64,
-                            2 => v.defence as u64,
-                            3 => v.strength as u64,
-                            4 => v.hitpoints as u64,
-                            5 => v.ranged as u64,
-                            6 => v.prayer as u64,
-                            7 => v.magic as u64,
-                            8 => v.cooking as u64,
-                            9 => v.woodcutting as u64,
-                            10 => v.fletching as u64,
-                            11 => v.fishing as u64,
-                            12 => v.firemaking as u64,
-                            13 => v.crafting as u64,
-                            14 => v.smithing as u64,
-                            15 => v.mining as u64,
-                            16 => v.herblore as u64,
-                            17 => v.agility as u64,
-                            18 => v.thieving as u64,
-                            19 => v.slayer as u64,
-                            20 => v.farming as u64,
-                            21 => v.runecraft as u64,
-                            22 => v.hunter as u64,
-                            23 => v.construction as u64,
-                            24 => v.ehp as u64,
-                            _ => unreachable!(),
-                        } as f64,
+    fn fetch_player(&mut self) {
+        self.information = None;
+        self.next_update_at = None;
+        self.dataset = None;
+        self.renames.clear();
+        self.kill_counts = None;
+        self.stats = None;
+        self.records = None;
+        self.gains = None;
+        self.collection_log = None;
+        self.reset_collection_log_lists();
+        let username = self.username.clone();
+        self.spawn(move |client| Message::Information {
+            result: client.player_information(&username),
+            username,
+        });
+        let username = self.username.clone();
+        let store = self.store.clone();
+        self.spawn(move |client| Message::Datapoints {
+            result: match store {
+                Some(store) => load_history(client, &store, &username),
+                None => client.merged_player_datapoints(&username, TimeRange::AllTime),
+            },
+            username,
+        });
+        let username = self.username.clone();
+        self.spawn(move |client| Message::KillCounts {
+            result: client.player_kill_count_datapoints(&username, TimeRange::AllTime),
+            username,
+        });
+        let username = self.username.clone();
+        self.spawn(move |client| Message::Stats {
+            result: client.player_stats(&username),
+            username,
+        });
+        let username = self.username.clone();
+        self.spawn(move |client| Message::Records {
+            result: client.player_records(&username),
+            username,
+        });
+        let username = self.username.clone();
+        self.spawn(move |client| Message::Gains {
+            result: client.player_gains(&username, RECORDS_GAINS_PERIOD),
+            username,
+        });
+        let username = self.username.clone();
+        self.spawn(move |client| Message::CollectionLog {
+            result: client.collection_log(&username),
+            username,
+        });
+    }
+
+    /// Time left until a new datapoint can be requested for the player, `None`
+    /// if it can be requested right away.
+    pub fn update_cooldown(&self) -> Option<Duration> {
+        self.next_update_at
+            .and_then(|at| at.checked_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Asks TempleOSRS to update the player, unless the datapoint cooldown
+    /// hasn't elapsed yet.
+    fn request_update(&mut self) {
+        if let Some(remaining) = self.update_cooldown() {
+            self.update_status = Some(format!(
+                "update available in {}",
+                render::countdown(remaining)
+            ));
+            return;
+        }
+        // Not known until the player information has been fetched again.
+        self.next_update_at = None;
+        self.update_status = Some("requesting update...".to_owned());
+        let username = self.username.clone();
+        self.spawn(move |client| Message::DatapointAdded {
+            result: client.add_datapoint(&username),
+            username,
+        });
+    }
+
+    fn fetch_competition(&mut self, id: u32) {
+        if self.competition_id != Some(id) {
+            self.competition = None;
+        }
+        self.competition_id = Some(id);
+        self.competition_fetched_at = Some(Instant::now());
+        self.spawn(move |client| Message::Competition {
+            id,
+            result: client.competition_info(id),
+        });
+    }
+
+    fn fetch_group(&mut self, id: u32) {
+        self.group_id = Some(id);
+        self.group = None;
+        self.group_gains = None;
+        self.spawn(move |client| Message::Group {
+            id,
+            result: client.group_info(id),
+        });
+        self.spawn(move |client| Message::GroupGains {
+            id,
+            result: client.group_gains(id, &Metric::Skill(Skill::Overall), Period::Week),
+        });
+    }
+
+    fn fetch_leaderboard(&mut self, skill: Skill) {
+        self.leaderboard_skill = Some(skill);
+        self.leaderboard = None;
+        self.leaderboard_rows = StatefulList::with_items(Vec::new());
+        let filters = self.leaderboard_filters.clone();
+        self.spawn(move |client| Message::Leaderboard {
+            result: client.skill_hiscores(skill, &filters),
+            skill,
+            filters,
+        });
+    }
+
+    fn next_tab(&mut self) {
+        self.tab = self.tab.next();
+        if self.tab == Tab::Leaderboard {
+            // Show the leaderboard of the skill selected in the chart tab.
+            let skill = match self.selection() {
+                Selection::Skill(skill) => skill,
+                Selection::Boss(_) | Selection::Activity(_) | Selection::Extra(_) => Skill::Overall,
+            };
+            if self.leaderboard_skill != Some(skill) {
+                self.leaderboard_filters.page = 1;
+                self.fetch_leaderboard(skill);
+            }
+        }
+    }
+
+    fn cycle_game_mode(&mut self) {
+        let Some(skill) = self
+            .leaderboard_skill
+            .filter(|_| self.tab == Tab::Leaderboard)
+        else {
+            return;
+        };
+        // All modes, then each known mode in turn, then back to all modes.
+        self.leaderboard_filters.game_mode = match self.leaderboard_filters.game_mode {
+            None => Some(GameMode::KNOWN[0]),
+            Some(mode) => GameMode::KNOWN
+                .into_iter()
+                .skip_while(|&known| known != mode)
+                .nth(1),
+        };
+        self.leaderboard_filters.page = 1;
+        self.fetch_leaderboard(skill);
+    }
+
+    fn change_page(&mut self, forward: bool) {
+        let Some(skill) = self
+            .leaderboard_skill
+            .filter(|_| self.tab == Tab::Leaderboard)
+        else {
+            return;
+        };
+        let page = &mut self.leaderboard_filters.page;
+        *page = if forward {
+            page.saturating_add(1)
+        } else {
+            page.saturating_sub(1).max(1)
+        };
+        self.fetch_leaderboard(skill);
+    }
+
+    /// Opens the chart of the player selected in the leaderboard.
+    fn open_selected_player(&mut self) {
+        let Some(Ok(rows)) = self
+            .leaderboard
+            .as_ref()
+            .filter(|_| self.tab == Tab::Leaderboard)
+        else {
+            return;
+        };
+        let Some(row) = self
+            .leaderboard_rows
+            .state
+            .selected()
+            .and_then(|i| rows.get(i))
+        else {
+            return;
+        };
+        self.input = row.player.clone();
+        self.submit_username();
+        self.tab = Tab::Chart;
+    }
+
+    /// Called on every iteration of the event loop, keeps the competition
+    /// leaderboard live while it's shown.
+    pub fn tick(&mut self) {
+        if self.tab != Tab::Competition {
+            return;
+        }
+        if let (Some(id), Some(fetched_at)) = (self.competition_id, self.competition_fetched_at) {
+            if fetched_at.elapsed() >= COMPETITION_REFRESH_INTERVAL {
+                self.fetch_competition(id);
+            }
+        }
+    }
+
+    fn reset_collection_log_lists(&mut self) {
+        let categories = match &self.collection_log {
+            Some(Ok(log)) => log
+                .categories()
+                .map(|category| {
+                    format!(
+                        "{} ({}/{})",
+                        category.name(),
+                        category.obtained(),
+                        category.total()
                     )
                 })
                 .collect(),
-        )
+            _ => Vec::new(),
+        };
+        self.collection_log_categories = StatefulList::with_items(categories);
+        self.collection_log_categories.next();
+        self.collection_log_items_focused = false;
+        self.refresh_collection_log_items();
+    }
+
+    /// Fills the item list with the items of the selected category.
+    fn refresh_collection_log_items(&mut self) {
+        let items = match (
+            &self.collection_log,
+            self.collection_log_categories.state.selected(),
+        ) {
+            (Some(Ok(log)), Some(selected)) => log
+                .categories()
+                .nth(selected)
+                .map(|category| {
+                    category
+                        .items
+                        .iter()
+                        .map(|item| {
+                            let mark = if item.obtained() { 'x' } else { ' ' };
+                            format!("[{mark}] {} ({})", item.name, item.count)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        self.collection_log_items = StatefulList::with_items(items);
+    }
+
+    fn select_next(&mut self) {
+        match self.tab {
+            Tab::Chart => self.skills.next(),
+            Tab::Stats | Tab::Records | Tab::Competition | Tab::Clan => {}
+            Tab::Leaderboard => self.leaderboard_rows.next(),
+            Tab::CollectionLog if self.collection_log_items_focused => {
+                self.collection_log_items.next();
+            }
+            Tab::CollectionLog => {
+                self.collection_log_categories.next();
+                self.refresh_collection_log_items();
+            }
+        }
+    }
+
+    fn select_previous(&mut self) {
+        match self.tab {
+            Tab::Chart => self.skills.previous(),
+            Tab::Stats | Tab::Records | Tab::Competition | Tab::Clan => {}
+            Tab::Leaderboard => self.leaderboard_rows.previous(),
+            Tab::CollectionLog if self.collection_log_items_focused => {
+                self.collection_log_items.previous();
+            }
+            Tab::CollectionLog => {
+                self.collection_log_categories.previous();
+                self.refresh_collection_log_items();
+            }
+        }
+    }
+
+    fn focus_collection_log_items(&mut self, focused: bool) {
+        if self.tab == Tab::CollectionLog {
+            self.collection_log_items_focused = focused;
+            if focused && self.collection_log_items.state.selected().is_none() {
+                self.collection_log_items.next();
+            }
+        }
+    }
+
+    /// Lists the metrics of the datapoints that aren't known skills.
+    fn refresh_extra_metrics(&mut self) {
+        let extra_metrics: BTreeSet<_> = self
+            .dataset
+            .iter()
+            .flatten()
+            .flat_map(|dataset| dataset.values())
+            .flat_map(|skills| skills.extra.keys().cloned())
+            .collect();
+        let extra_start = Skill::ALL.len() + Boss::ALL.len() + Activity::ALL.len();
+        self.skills.items.truncate(extra_start);
+        self.skills.items.extend(extra_metrics.iter().cloned());
+        self.extra_metrics = extra_metrics.into_iter().collect();
+        if self.skills.state.selected() >= Some(self.skills.items.len()) {
+            self.skills.state.select(Some(0));
+        }
+    }
+
+    pub fn selection(&self) -> Selection {
+        let selected = self.skills.state.selected().expect("a selected option");
+        let boss_start = Skill::ALL.len();
+        let activity_start = boss_start + Boss::ALL.len();
+        let extra_start = activity_start + Activity::ALL.len();
+        if selected < boss_start {
+            Selection::Skill(Skill::ALL[selected])
+        } else if selected < activity_start {
+            Selection::Boss(Boss::ALL[selected - boss_start])
+        } else if selected < extra_start {
+            Selection::Activity(Activity::ALL[selected - activity_start])
+        } else {
+            Selection::Extra(selected - extra_start)
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn get_data(&self) -> Option<Vec<(f64, f64)>> {
+        let skill = match self.selection() {
+            Selection::Skill(skill) => skill,
+            Selection::Boss(boss) => return self.get_kill_counts(|kc| kc.bosses.get(boss)),
+            Selection::Activity(activity) => {
+                return self.get_kill_counts(|kc| kc.activities.get(activity))
+            }
+            Selection::Extra(index) => return self.get_extra(&self.extra_metrics[index]),
+        };
+        let dataset = self.dataset.as_ref()?.as_ref().ok()?;
+        let data: Vec<_> = dataset
+            .iter()
+            .map(|(k, v)| (k.0.timestamp() as f64, v.get(skill) as f64))
+            .collect();
+        (!data.is_empty()).then_some(data)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn get_extra(&self, name: &str) -> Option<Vec<(f64, f64)>> {
+        let dataset = self.dataset.as_ref()?.as_ref().ok()?;
+        // Older datapoints don't have metrics that were added later on.
+        let data: Vec<_> = dataset
+            .iter()
+            .filter_map(|(k, v)| Some((k.0.timestamp() as f64, v.extra(name)?)))
+            .collect();
+        (!data.is_empty()).then_some(data)
+    }
+
+    #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+    fn get_kill_counts(
+        &self,
+        count: impl Fn(&KillCounts) -> Option<u32>,
+    ) -> Option<Vec<(f64, f64)>> {
+        let kill_counts = self.kill_counts.as_ref()?.as_ref().ok()?;
+        // Datapoints from before the player got ranked have no kill count.
+        let data: Vec<_> = kill_counts
+            .iter()
+            .filter_map(|(k, v)| Some((k.0.timestamp() as f64, count(v)? as f64)))
+            .collect();
+        (!data.is_empty()).then_some(data)
     }
 
     fn move_cursor_left(&mut self) {
@@ -205,20 +902,70 @@ impl App {
         new_cursor_pos.clamp(0, self.input.len())
     }
 
+    /// The value of the current [`InputTarget`], shown in the textbox when not editing.
+    pub fn input_value(&self) -> String {
+        match self.tab.input_target() {
+            InputTarget::Username => self.username.clone(),
+            InputTarget::CompetitionId => self
+                .competition_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            InputTarget::GroupId => self.group_id.map(|id| id.to_string()).unwrap_or_default(),
+            InputTarget::Country => self.leaderboard_filters.country.clone().unwrap_or_default(),
+        }
+    }
+
+    fn start_editing(&mut self) {
+        self.input = self.input_value();
+        self.cursor_position = self.input.len();
+        self.input_mode = InputMode::Editing;
+    }
+
+    fn submit(&mut self) {
+        match self.tab.input_target() {
+            InputTarget::Username => self.submit_username(),
+            InputTarget::Country => {
+                let country = self.input.trim().to_uppercase();
+                self.leaderboard_filters.country = (!country.is_empty()).then_some(country);
+                self.leaderboard_filters.page = 1;
+                if let Some(skill) = self.leaderboard_skill {
+                    self.fetch_leaderboard(skill);
+                }
+                self.input_mode = InputMode::Normal;
+            }
+            target => {
+                // Keep editing until the input is a valid ID.
+                let Ok(id) = self.input.trim().parse() else {
+                    return;
+                };
+                if target == InputTarget::GroupId {
+                    self.fetch_group(id);
+                } else {
+                    self.fetch_competition(id);
+                }
+                self.input_mode = InputMode::Normal;
+            }
+        }
+    }
+
     fn submit_username(&mut self) {
         self.username = self.input.clone();
-        self.dataset = self
-            .client
-            .player_datapoints(&self.input, 1_000_000_000)
-            .ok();
+        self.update_status = None;
+        self.fetch_player();
         self.input_mode = InputMode::Normal;
     }
 }
 
 pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
     loop {
+        app.receive();
+        app.tick();
         terminal.draw(|f| ui(f, &mut app))?;
 
+        // Wake up regularly to pick up results from background requests.
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
         if let Event::Key(key) = event::read()? {
             if key.kind != KeyEventKind::Press {
                 continue;
@@ -227,15 +974,23 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Resu
                 InputMode::Normal => {
                     match key.code {
                         KeyCode::Char('q') => return Ok(()),
-                        KeyCode::Down => app.skills.next(),
-                        KeyCode::Up => app.skills.previous(),
+                        KeyCode::Down => app.select_next(),
+                        KeyCode::Up => app.select_previous(),
+                        KeyCode::Right => app.focus_collection_log_items(true),
+                        KeyCode::Left => app.focus_collection_log_items(false),
+                        KeyCode::Tab => app.next_tab(),
+                        KeyCode::Enter => app.open_selected_player(),
+                        KeyCode::Char('g') => app.cycle_game_mode(),
+                        KeyCode::Char('n') => app.change_page(true),
+                        KeyCode::Char('p') => app.change_page(false),
+                        KeyCode::Char('u') => app.request_update(),
                         // KeyCode::Esc => app.skills.unselect(),
-                        KeyCode::Char('e') => app.input_mode = InputMode::Editing,
+                        KeyCode::Char('e') => app.start_editing(),
                         _ => {}
                     };
                 }
                 InputMode::Editing => match key.code {
-                    KeyCode::Enter => app.submit_username(),
+                    KeyCode::Enter => app.submit(),
                     KeyCode::Char(to_insert) => app.enter_char(to_insert),
                     KeyCode::Backspace => app.delete_char(),
                     KeyCode::Left => app.move_cursor_left(),
@@ -250,11 +1005,12 @@ pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Resu
 }
 
 fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-    let [edit_message_chunk, edit_chunk, main_chunk] = *Layout::default()
+    let [edit_message_chunk, edit_chunk, tabs_chunk, main_chunk] = *Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1),
             Constraint::Length(3),
+            Constraint::Length(1),
             Constraint::Min(1),
         ])
         .split(f.size())
@@ -262,19 +1018,90 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         return;
     };
 
+    render::textbox(f, app, edit_message_chunk, edit_chunk);
+    render::tabs(f, app, tabs_chunk);
+
+    match app.tab {
+        Tab::Chart => chart_tab(f, app, main_chunk),
+        Tab::Stats => {
+            let [stats_chunk, information_chunk] = *Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(60), Constraint::Length(40)].as_ref())
+                .split(main_chunk)
+            else {
+                return;
+            };
+            // Both can be loading or failed at once, so each popup stays in its own panel.
+            match &app.stats {
+                Some(Ok(stats)) => render::stats(f, stats, stats_chunk),
+                state => render::popup_in(
+                    f,
+                    &app.username,
+                    state,
+                    render::centered_rect(70, 30, stats_chunk),
+                ),
+            }
+            match &app.information {
+                Some(Ok(information)) => render::information(f, information, information_chunk),
+                state => render::popup_in(
+                    f,
+                    &app.username,
+                    state,
+                    render::centered_rect(100, 30, information_chunk),
+                ),
+            }
+        }
+        Tab::Records => match &app.records {
+            Some(Ok(records)) => render::records(f, records, &app.gains, main_chunk),
+            state => render::popup(f, &app.username, state),
+        },
+        Tab::CollectionLog => {
+            if let Some(Ok(_)) = &app.collection_log {
+                render::collection_log(f, app, main_chunk);
+            } else {
+                render::popup(f, &app.username, &app.collection_log);
+            }
+        }
+        Tab::Competition => match (app.competition_id, &app.competition) {
+            (None, _) => render::hint(f, "Press e to enter a competition ID.", main_chunk),
+            (Some(_), Some(Ok(competition))) => render::competition(f, competition, main_chunk),
+            (Some(id), state) => render::popup(f, &format!("competition {id}"), state),
+        },
+        Tab::Leaderboard => match &app.leaderboard {
+            Some(Ok(_)) => render::leaderboard(f, app, main_chunk),
+            state => {
+                let skill = app.leaderboard_skill.unwrap_or(Skill::Overall);
+                render::popup(f, &format!("{skill} leaderboard"), state);
+            }
+        },
+        Tab::Clan => match (app.group_id, &app.group) {
+            (None, _) => render::hint(f, "Press e to enter a group ID.", main_chunk),
+            (Some(_), Some(Ok(group))) => render::clan(f, group, &app.group_gains, main_chunk),
+            (Some(id), state) => render::popup(f, &format!("group {id}"), state),
+        },
+    }
+}
+
+fn chart_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, main_chunk: Rect) {
     let [items_chunk, graph_chunk] = *Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Length(15), Constraint::Percentage(75)].as_ref())
+        .constraints([Constraint::Length(26), Constraint::Percentage(75)].as_ref())
         .split(main_chunk)
     else {
         return;
     };
 
-    render::textbox(f, app, edit_message_chunk, edit_chunk);
     render::items(f, app, items_chunk);
 
     let Some(experience) = app.get_data() else {
-        render::popup(f, app);
+        match app.selection() {
+            Selection::Skill(_) | Selection::Extra(_) => {
+                render::popup(f, &app.username, &app.dataset);
+            }
+            Selection::Boss(_) | Selection::Activity(_) => {
+                render::popup(f, &app.username, &app.kill_counts);
+            }
+        }
         return;
     };
 
@@ -282,24 +1109,71 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
 }
 
 mod render {
-    use super::{App, InputMode};
+    use super::{App, InputMode, InputTarget, Selection, Tab, RECORDS_GAINS_PERIOD};
+    use crate::api::{
+        types::{
+            Competition, Group, GroupMemberGain, Metric, Period, PlayerGains, PlayerInformation,
+            PlayerRecords, PlayerStats, Record, Skill, Timestamp,
+        },
+        ApiError,
+    };
     use chrono::{TimeZone, Utc};
     use num_format::{Locale, ToFormattedString};
     use ratatui::{
         prelude::*,
-        widgets::{Axis, Block, Borders, Chart, Clear, Dataset, List, ListItem, Paragraph},
+        widgets::{
+            Axis, Block, Borders, Cell, Chart, Clear, Dataset, List, ListItem, Paragraph, Row,
+            Table, Tabs, Wrap,
+        },
         Frame,
     };
+    use std::time::Duration;
+
+    /// Shows why `state` has nothing to display: it's still loading, or the request failed.
+    /// `subject` is what the data is about, e.g. a username.
+    pub fn popup<B: Backend, T>(
+        f: &mut Frame<B>,
+        subject: &str,
+        state: &Option<Result<T, ApiError>>,
+    ) {
+        popup_in(f, subject, state, centered_rect(40, 15, f.size()));
+    }
 
-    pub fn popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-        let block = Block::default().title("Error").borders(Borders::ALL);
-        let text = Paragraph::new(format!(
-            "Failed to get data for user: \"{}\".",
-            app.username
-        ))
-        .block(block)
-        .alignment(Alignment::Center);
-        let area = centered_rect(40, 15, f.size());
+    /// [`popup`] drawn in `area`, for screens showing more than one popup at once.
+    pub fn popup_in<B: Backend, T>(
+        f: &mut Frame<B>,
+        subject: &str,
+        state: &Option<Result<T, ApiError>>,
+        area: Rect,
+    ) {
+        let mut lines = vec![Line::from(format!(
+            "Failed to get data for: \"{subject}\"."
+        ))];
+        let title = match state {
+            None => {
+                lines = vec![Line::from(format!("Loading data for: \"{subject}\"..."))];
+                "Loading".to_owned()
+            }
+            Some(Err(error)) => {
+                lines.push(Line::from(error.to_string()));
+                if let Some(payload) = error.payload() {
+                    lines.push(Line::from(format!("TempleOSRS: {payload}")));
+                }
+                if error.is_player_not_tracked() {
+                    lines.push(Line::from("Press u to start tracking the player."));
+                }
+                format!("Error: {}", error.kind())
+            }
+            Some(Ok(_)) => {
+                lines.push(Line::from("No datapoints available."));
+                "Error".to_owned()
+            }
+        };
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let text = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
         f.render_widget(Clear, area); //this clears out the background
         f.render_widget(text, area);
     }
@@ -310,6 +1184,7 @@ mod render {
         message_chunk: Rect,
         edit_chunk: Rect,
     ) {
+        let target = app.tab.input_target().title();
         let (msg, style) = match app.input_mode {
             InputMode::Normal => (
                 vec![
@@ -317,7 +1192,9 @@ mod render {
                     "q".bold(),
                     " to exit, ".into(),
                     "e".bold(),
-                    " to edit username.".bold(),
+                    Span::from(format!(" to edit {}, ", target.to_lowercase())).bold(),
+                    "Tab".bold(),
+                    " to switch view.".into(),
                 ],
                 Style::default().add_modifier(Modifier::RAPID_BLINK),
             ),
@@ -327,7 +1204,7 @@ mod render {
                     "Esc".bold(),
                     " to stop editing, ".into(),
                     "Enter".bold(),
-                    " to submit username".into(),
+                    format!(" to submit {}", target.to_lowercase()).into(),
                 ],
                 Style::default(),
             ),
@@ -338,25 +1215,413 @@ mod render {
         let help_message = Paragraph::new(text);
         f.render_widget(help_message, message_chunk);
 
-        let input = Paragraph::new(app.input.as_str())
-            .style(match app.input_mode {
-                InputMode::Normal => Style::default(),
-                InputMode::Editing => Style::default().fg(Color::Yellow),
-            })
-            .block(Block::default().borders(Borders::ALL).title("Username"));
+        let (value, style) = match app.input_mode {
+            InputMode::Normal => (app.input_value(), Style::default()),
+            InputMode::Editing => (app.input.clone(), Style::default().fg(Color::Yellow)),
+        };
+        let mut title = target.to_owned();
+        if app.tab.input_target() == InputTarget::Username {
+            if let Some(status) = &app.update_status {
+                title.push_str(&format!(" - {status}"));
+            }
+            match app.update_cooldown() {
+                Some(remaining) => {
+                    title.push_str(&format!(" (next update in {})", countdown(remaining)));
+                }
+                None => title.push_str(" (press u to update)"),
+            }
+        }
+        let input = Paragraph::new(value)
+            .style(style)
+            .block(Block::default().borders(Borders::ALL).title(title));
         f.render_widget(input, edit_chunk);
     }
 
+    pub fn tabs<B: Backend>(f: &mut Frame<B>, app: &App, chunk: Rect) {
+        let titles = Tab::ALL.iter().map(|tab| Line::from(tab.title())).collect();
+        let selected = Tab::ALL.iter().position(|&tab| tab == app.tab);
+        let tabs = Tabs::new(titles)
+            .select(selected.unwrap_or(0))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+            );
+        f.render_widget(tabs, chunk);
+    }
+
+    pub fn stats<B: Backend>(f: &mut Frame<B>, stats: &PlayerStats, chunk: Rect) {
+        let optional = |value: Option<u32>| {
+            value.map_or_else(
+                || "-".to_owned(),
+                |value| value.to_formatted_string(&Locale::en),
+            )
+        };
+        let rows = stats.skills.iter().map(|skill| {
+            Row::new(vec![
+                Cell::from(skill.skill.name()),
+                Cell::from(optional(skill.level)),
+                Cell::from(skill.xp.to_formatted_string(&Locale::en)),
+                Cell::from(optional(skill.rank)),
+                Cell::from(format!("{:.2}", skill.ehp)),
+            ])
+        });
+        let title = match &stats.date {
+            Some(date) => format!("Current stats ({})", date.0.format("%Y-%m-%d %H:%M")),
+            None => "Current stats".to_owned(),
+        };
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec!["Skill", "Level", "Experience", "Rank", "EHP"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .widths(&[
+                Constraint::Length(15),
+                Constraint::Length(6),
+                Constraint::Length(13),
+                Constraint::Length(11),
+                Constraint::Length(10),
+            ]);
+        f.render_widget(table, chunk);
+    }
+
+    pub fn records<B: Backend>(
+        f: &mut Frame<B>,
+        records: &PlayerRecords,
+        gains: &Option<Result<PlayerGains, ApiError>>,
+        chunk: Rect,
+    ) {
+        let record = |record: Option<&Record>| {
+            record.map_or_else(
+                || "-".to_owned(),
+                |record| record.gained.to_formatted_string(&Locale::en),
+            )
+        };
+        let gained = |metric: &Metric| match gains {
+            Some(Ok(gains)) => {
+                let gained = match metric {
+                    Metric::Skill(skill) => gains.skill(*skill).map(|gain| gain.xp),
+                    _ => gains.boss(metric.key()).map(|gain| gain.kc),
+                };
+                gained.map_or_else(
+                    || "-".to_owned(),
+                    |gained| gained.to_formatted_string(&Locale::en),
+                )
+            }
+            Some(Err(error)) => error.kind().to_owned(),
+            None => "Loading...".to_owned(),
+        };
+        let rows = records
+            .records
+            .iter()
+            .filter(|records| {
+                records.day.is_some() || records.week.is_some() || records.month.is_some()
+            })
+            .map(|records| {
+                Row::new(vec![
+                    Cell::from(records.metric.to_string()),
+                    Cell::from(gained(&records.metric)),
+                    Cell::from(record(records.best(Period::Day))),
+                    Cell::from(record(records.best(Period::Week))),
+                    Cell::from(record(records.best(Period::Month))),
+                ])
+            });
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec![
+                    "Metric".to_owned(),
+                    format!("This {}", RECORDS_GAINS_PERIOD.to_string().to_lowercase()),
+                    "Best day".to_owned(),
+                    "Best week".to_owned(),
+                    "Best month".to_owned(),
+                ])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Personal bests"),
+            )
+            .widths(&[
+                Constraint::Length(26),
+                Constraint::Length(13),
+                Constraint::Length(13),
+                Constraint::Length(13),
+                Constraint::Length(13),
+            ]);
+        f.render_widget(table, chunk);
+    }
+
+    pub fn information<B: Backend>(f: &mut Frame<B>, information: &PlayerInformation, chunk: Rect) {
+        let yes_no = |value: bool| if value { "Yes" } else { "No" };
+        let date = |timestamp: Option<Timestamp>| {
+            timestamp.map_or_else(
+                || "-".to_owned(),
+                |timestamp| timestamp.0.format("%Y-%m-%d %H:%M").to_string(),
+            )
+        };
+        let rows = [
+            ("Country", information.country.clone()),
+            ("Game mode", information.game_mode.to_string()),
+            (
+                "Fresh start",
+                yes_no(information.fresh_start_account).to_owned(),
+            ),
+            ("Level 3", yes_no(information.combat_level_3).to_owned()),
+            ("F2P", yes_no(information.f2p).to_owned()),
+            ("Banned", yes_no(information.banned).to_owned()),
+            ("Disqualified", yes_no(information.disqualified).to_owned()),
+            ("Last checked", date(information.last_checked)),
+            ("Last changed", date(information.last_changed)),
+        ]
+        .into_iter()
+        .map(|(name, value)| Row::new(vec![Cell::from(name), Cell::from(value)]));
+        let table = Table::new(rows)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(information.username.clone()),
+            )
+            .widths(&[Constraint::Length(13), Constraint::Length(24)]);
+        f.render_widget(table, chunk);
+    }
+
+    pub fn hint<B: Backend>(f: &mut Frame<B>, hint: &str, chunk: Rect) {
+        let text = Paragraph::new(hint)
+            .block(Block::default().borders(Borders::ALL))
+            .alignment(Alignment::Center);
+        f.render_widget(text, chunk);
+    }
+
+    pub fn competition<B: Backend>(f: &mut Frame<B>, competition: &Competition, chunk: Rect) {
+        let info = &competition.info;
+        let status = if info.has_ended() {
+            "ended"
+        } else if info.has_started() {
+            "ongoing"
+        } else {
+            "upcoming"
+        };
+        let title = format!(
+            "{} - {} ({} to {}, {status})",
+            info.name,
+            info.metric,
+            info.start.0.format("%Y-%m-%d %H:%M"),
+            info.end.0.format("%Y-%m-%d %H:%M"),
+        );
+        let unit = if info.metric.is_kill_count() {
+            "KC"
+        } else {
+            "XP"
+        };
+        let rows = competition
+            .standings()
+            .into_iter()
+            .enumerate()
+            .map(|(rank, participant)| {
+                Row::new(vec![
+                    Cell::from((rank + 1).to_string()),
+                    Cell::from(participant.player.clone()),
+                    Cell::from(participant.start_xp.to_formatted_string(&Locale::en)),
+                    Cell::from(participant.end_xp.to_formatted_string(&Locale::en)),
+                    Cell::from(participant.gained.to_formatted_string(&Locale::en)),
+                ])
+            });
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec![
+                    "#".to_owned(),
+                    "Player".to_owned(),
+                    format!("Start {unit}"),
+                    format!("End {unit}"),
+                    "Gained".to_owned(),
+                ])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .widths(&[
+                Constraint::Length(5),
+                Constraint::Length(14),
+                Constraint::Length(13),
+                Constraint::Length(13),
+                Constraint::Length(13),
+            ]);
+        f.render_widget(table, chunk);
+    }
+
+    pub fn clan<B: Backend>(
+        f: &mut Frame<B>,
+        group: &Group,
+        gains: &Option<Result<Vec<GroupMemberGain>, ApiError>>,
+        chunk: Rect,
+    ) {
+        let gained = |player: &str| match gains {
+            Some(Ok(gains)) => gains
+                .iter()
+                .find(|gain| gain.player.eq_ignore_ascii_case(player))
+                .map(|gain| gain.gained),
+            _ => None,
+        };
+        let mut members: Vec<_> = group
+            .members
+            .iter()
+            .map(|member| (member, gained(&member.player)))
+            .collect();
+        members.sort_by_key(|(_, gained)| std::cmp::Reverse(*gained));
+
+        let gains_header = match gains {
+            None => "Week XP (loading)",
+            Some(Err(_)) => "Week XP (failed)",
+            Some(Ok(_)) => "Week XP",
+        };
+        let rows = members.into_iter().map(|(member, gained)| {
+            Row::new(vec![
+                Cell::from(member.player.clone()),
+                Cell::from(format!("{:.2}", member.ehp)),
+                Cell::from(member.last_checked.as_ref().map_or_else(
+                    || "-".to_owned(),
+                    |date| date.0.format("%Y-%m-%d %H:%M").to_string(),
+                )),
+                Cell::from(gained.map_or_else(
+                    || "-".to_owned(),
+                    |gained| gained.to_formatted_string(&Locale::en),
+                )),
+            ])
+        });
+        let title = format!("{} ({} members)", group.info.name, group.members.len());
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec!["Player", "EHP", "Last checked", gains_header])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .widths(&[
+                Constraint::Length(14),
+                Constraint::Length(10),
+                Constraint::Length(18),
+                Constraint::Length(18),
+            ]);
+        f.render_widget(table, chunk);
+    }
+
+    pub fn leaderboard<B: Backend>(f: &mut Frame<B>, app: &mut App, chunk: Rect) {
+        let filters = &app.leaderboard_filters;
+        let title = format!(
+            "{} leaderboard - page {}, {}, {} (Enter: open chart, g: game mode, n/p: page)",
+            app.leaderboard_skill.unwrap_or(Skill::Overall),
+            filters.page,
+            filters
+                .game_mode
+                .map_or_else(|| "all game modes".to_owned(), |mode| mode.to_string()),
+            filters.country.as_deref().unwrap_or("all countries"),
+        );
+        let rows = List::new(
+            app.leaderboard_rows
+                .items
+                .iter()
+                .map(|i| ListItem::new(i.clone()))
+                .collect::<Vec<_>>(),
+        )
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(
+            Style::default()
+                .bg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        );
+        f.render_stateful_widget(rows, chunk, &mut app.leaderboard_rows.state);
+    }
+
+    pub fn collection_log<B: Backend>(f: &mut Frame<B>, app: &mut App, chunk: Rect) {
+        let [categories_chunk, items_chunk] = *Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(45), Constraint::Min(1)].as_ref())
+            .split(chunk)
+        else {
+            return;
+        };
+        let title = match &app.collection_log {
+            Some(Ok(log)) => format!(
+                "Categories ({}/{})",
+                log.total_collections_finished, log.total_collections_available
+            ),
+            _ => "Categories".to_owned(),
+        };
+        let focused = Style::default().fg(Color::LightGreen);
+        let (categories_style, items_style) = if app.collection_log_items_focused {
+            (Style::default(), focused)
+        } else {
+            (focused, Style::default())
+        };
+        let highlight_style = Style::default()
+            .bg(Color::LightGreen)
+            .add_modifier(Modifier::BOLD);
+
+        let categories = List::new(
+            app.collection_log_categories
+                .items
+                .iter()
+                .map(|i| ListItem::new(i.clone()))
+                .collect::<Vec<_>>(),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(categories_style)
+                .title(title),
+        )
+        .highlight_style(highlight_style);
+        f.render_stateful_widget(
+            categories,
+            categories_chunk,
+            &mut app.collection_log_categories.state,
+        );
+
+        let items = List::new(
+            app.collection_log_items
+                .items
+                .iter()
+                .map(|i| ListItem::new(i.clone()))
+                .collect::<Vec<_>>(),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(items_style)
+                .title("Items"),
+        )
+        .highlight_style(highlight_style);
+        f.render_stateful_widget(items, items_chunk, &mut app.collection_log_items.state);
+    }
+
     pub fn items<B: Backend>(f: &mut Frame<B>, app: &mut App, chunk: Rect) {
+        // Levels and experience at the latest datapoint, next to each skill.
+        let latest = app
+            .dataset
+            .as_ref()
+            .and_then(|dataset| dataset.as_ref().ok())
+            .and_then(|dataset| dataset.values().next_back());
         let items: Vec<ListItem> = app
             .skills
             .items
             .iter()
-            .map(|i| ListItem::new(i.clone()))
+            .enumerate()
+            .map(|(index, name)| match (latest, Skill::ALL.get(index)) {
+                (Some(skills), Some(&skill)) => ListItem::new(format!(
+                    "{name:<12}{:>4} {:>7}",
+                    skills.level(skill),
+                    compact(skills.get(skill)),
+                )),
+                _ => ListItem::new(name.clone()),
+            })
             .collect();
 
+        let title = match latest {
+            Some(skills) => format!("Skill / Boss (combat {})", skills.combat_level()),
+            None => "Skill / Boss".to_owned(),
+        };
         let items = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title("Skill"))
+            .block(Block::default().borders(Borders::ALL).title(title))
             .highlight_style(
                 Style::default()
                     .bg(Color::LightGreen)
@@ -366,6 +1631,16 @@ mod render {
         f.render_stateful_widget(items, chunk, &mut app.skills.state);
     }
 
+    /// Short form of a large number, e.g. `13.0M` or `83.0K`.
+    #[allow(clippy::cast_precision_loss)]
+    fn compact(value: u64) -> String {
+        match value {
+            0..=9_999 => value.to_string(),
+            10_000..=999_999 => format!("{:.1}K", value as f64 / 1e3),
+            _ => format!("{:.1}M", value as f64 / 1e6),
+        }
+    }
+
     #[allow(
         clippy::cast_possible_truncation,
         clippy::cast_lossless,
@@ -385,19 +1660,62 @@ mod render {
             .style(Style::default().fg(Color::White))
             .data(experience);
 
-        let hunter = app
-            .dataset
-            .as_ref()
-            .unwrap()
-            .iter()
-            .map(|(k, v)| (k.0.timestamp() as f64, v.hunter as f64))
-            .collect::<Vec<_>>();
-        let dataset2 = Dataset::default()
-            .name("Hunter")
-            .marker(symbols::Marker::Braille)
-            .graph_type(ratatui::widgets::GraphType::Line)
-            .style(Style::default().fg(Color::Green))
-            .data(&hunter);
+        let (title, hunter) = match app.selection() {
+            Selection::Skill(_) => (
+                "Experience",
+                app.dataset
+                    .as_ref()
+                    .and_then(|dataset| dataset.as_ref().ok())
+                    .unwrap()
+                    .iter()
+                    .map(|(k, v)| (k.0.timestamp() as f64, v.get(Skill::Hunter) as f64))
+                    .collect::<Vec<_>>(),
+            ),
+            Selection::Extra(_) => ("Experience", Vec::new()),
+            Selection::Boss(_) | Selection::Activity(_) => ("Kill count", Vec::new()),
+        };
+        let mut datasets = vec![dataset];
+        if !hunter.is_empty() {
+            datasets.push(
+                Dataset::default()
+                    .name("Hunter")
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(ratatui::widgets::GraphType::Line)
+                    .style(Style::default().fg(Color::Green))
+                    .data(&hunter),
+            );
+        }
+
+        // Vertical markers at the points where the player changed their name.
+        let renames: Vec<_> = match app.selection() {
+            Selection::Skill(_) | Selection::Extra(_) => app
+                .renames
+                .iter()
+                .map(|rename| {
+                    let x = rename.changed_at.0.timestamp() as f64;
+                    let top = experience.last().unwrap().1;
+                    (
+                        format!("Renamed from {}", rename.old_name),
+                        vec![(x, 0.), (x, top)],
+                    )
+                })
+                .filter(|(_, line)| {
+                    (experience.first().unwrap().0..=experience.last().unwrap().0)
+                        .contains(&line[0].0)
+                })
+                .collect(),
+            Selection::Boss(_) | Selection::Activity(_) => Vec::new(),
+        };
+        for (name, line) in &renames {
+            datasets.push(
+                Dataset::default()
+                    .name(name.as_str())
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(ratatui::widgets::GraphType::Line)
+                    .style(Style::default().fg(Color::Yellow))
+                    .data(line),
+            );
+        }
 
         let start_date = Utc
             .timestamp_opt(experience.first().unwrap().0 as i64, 0)
@@ -408,8 +1726,8 @@ mod render {
         let time_difference = end_date - start_date;
         let mid_point = start_date + time_difference / 2;
 
-        let chart = Chart::new(vec![dataset, dataset2])
-            .block(Block::default().borders(Borders::ALL).title("Experience"))
+        let chart = Chart::new(datasets)
+            .block(Block::default().borders(Borders::ALL).title(title))
             .x_axis(
                 Axis::default()
                     .title("Time")
@@ -440,8 +1758,19 @@ mod render {
         f.render_widget(chart, chunk);
     }
 
+    /// Formats a duration as `MM:SS`, or `HH:MM:SS` when it's an hour or longer.
+    pub fn countdown(duration: Duration) -> String {
+        let seconds = duration.as_secs();
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        if hours > 0 {
+            format!("{hours:02}:{minutes:02}:{seconds:02}")
+        } else {
+            format!("{minutes:02}:{seconds:02}")
+        }
+    }
+
     /// helper function to create a centered rect using up certain percentage of the available rect `r`
-    fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         let popup_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints(